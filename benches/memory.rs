@@ -0,0 +1,97 @@
+//! Compares the peak resident memory of the CLI parsing a large file that it
+//! reads into a `String` against one that it memory-maps.
+//!
+//! Expect the two peaks to be close: mapped pages count as resident once
+//! the parser has touched them. What differs is that they are clean pages
+//! backed by the file, which the kernel can reclaim under memory pressure,
+//! whereas the `String` is anonymous memory that can only be swapped.
+//!
+//! Run with `cargo bench --features mmap`. Each measurement runs the binary
+//! under a fresh copy of this bench, because the kernel only reports the
+//! largest of all the children a process has waited for.
+
+use std::ffi::c_int;
+use std::fs;
+use std::process::{Command, Stdio};
+
+const RUSAGE_CHILDREN: c_int = -1;
+
+/// `struct rusage`, of which only `ru_maxrss` is read.
+#[repr(C)]
+struct Rusage {
+    utime: [i64; 2],
+    stime: [i64; 2],
+    maxrss: i64,
+    rest: [i64; 13],
+}
+
+extern "C" {
+    fn getrusage(who: c_int, usage: *mut Rusage) -> c_int;
+}
+
+/// Runs the binary with `args` and returns the peak resident memory it
+/// reached, in the units `getrusage` uses: KiB on Linux, bytes on macOS.
+fn run_and_measure(args: &[String]) -> i64 {
+    let status = Command::new(env!("CARGO_BIN_EXE_rjson"))
+        .args(args)
+        .stdout(Stdio::null())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut usage = Rusage {
+        utime: [0; 2],
+        stime: [0; 2],
+        maxrss: 0,
+        rest: [0; 13],
+    };
+    // SAFETY: `usage` is a valid, writable `struct rusage`.
+    assert_eq!(unsafe { getrusage(RUSAGE_CHILDREN, &mut usage) }, 0);
+    usage.maxrss
+}
+
+fn measure(args: &[&str]) -> i64 {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .env("RJSON_BENCH_ARGS", args.join("\n"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+fn main() {
+    if let Ok(args) = std::env::var("RJSON_BENCH_ARGS") {
+        let args: Vec<String> = args.lines().map(str::to_owned).collect();
+        println!("{}", run_and_measure(&args));
+        return;
+    }
+
+    // Long strings keep the parsed tree small next to the file, so the copy
+    // of the input dominates the read-to-string peak.
+    let padding = "x".repeat(1000);
+    let mut document = String::from("[");
+    for i in 0..100_000 {
+        if i > 0 {
+            document.push(',');
+        }
+        document.push_str(&format!("{{\"id\":{},\"text\":\"{}\"}}", i, padding));
+    }
+    document.push(']');
+
+    let path = std::env::temp_dir().join(format!("rjson-bench-{}.json", std::process::id()));
+    fs::write(&path, &document).unwrap();
+    let path = path.to_str().unwrap();
+
+    let read = measure(&["--time", path]);
+    let mapped = measure(&["--time", "--mmap", path]);
+    fs::remove_file(path).unwrap();
+
+    println!("input size:     {} KiB", document.len() / 1024);
+    println!("read to string: {} peak RSS", read);
+    println!("memory-mapped:  {} peak RSS", mapped);
+}
@@ -0,0 +1,73 @@
+//! Generates arbitrary [`Value`] trees for property tests.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::num::f64;
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+
+use crate::Value;
+
+/// Generates documents up to eight levels deep whose every value survives a
+/// trip through the serializer and back.
+///
+/// So numbers are finite, since the others serialize as `null`, and
+/// [`RawNumber`](Value::RawNumber) is never produced, since it parses back
+/// as an [`Integer`](Value::Integer) or [`Number`](Value::Number). Trees
+/// shrink towards scalars, and scalars towards `null`.
+impl Arbitrary for Value {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Value>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::Bool),
+            any::<i64>().prop_map(Value::Integer),
+            (f64::POSITIVE | f64::NEGATIVE | f64::NORMAL | f64::SUBNORMAL | f64::ZERO)
+                .prop_map(Value::Number),
+            any::<String>().prop_map(Value::String),
+        ];
+
+        leaf.prop_recursive(8, 256, 10, |inner| {
+            prop_oneof![
+                vec(inner.clone(), 0..10).prop_map(Value::Array),
+                vec((any::<String>(), inner), 0..10).prop_map(Value::Object),
+            ]
+        })
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::{escape_string, parse, to_writer, unescape_string, Value};
+
+    proptest! {
+        #[test]
+        fn test_round_trip(value: Value) {
+            prop_assert_eq!(parse(&value.serialize()), Ok(value.clone()));
+            prop_assert_eq!(parse(&value.serialize_pretty()), Ok(value.clone()));
+            prop_assert_eq!(parse(&value.serialize_ascii()), Ok(value.clone()));
+            prop_assert_eq!(parse(&value.serialize_html_safe()), Ok(value.clone()));
+
+            let mut buffer = Vec::new();
+            to_writer(&value, &mut buffer).unwrap();
+            prop_assert_eq!(buffer, value.serialize().into_bytes());
+        }
+
+        #[test]
+        fn test_round_trip_canonical(value: Value) {
+            let canonical = parse(&value.serialize_canonical()).unwrap();
+            prop_assert!(canonical.semantic_eq(&value));
+            prop_assert_eq!(canonical.serialize_canonical(), value.serialize_canonical());
+        }
+
+        #[test]
+        fn test_escape_round_trip(string: String) {
+            prop_assert_eq!(unescape_string(&escape_string(&string)), Ok(string));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
+use crate::parser::{Builder, Parser, Scalar};
+use crate::{ParseError, ParserOptions, Value};
+
+/// A parsed JSON value that borrows its strings from the input.
+///
+/// Strings and keys without escape sequences point straight into the parsed
+/// text; only those that need unescaping are allocated.
+#[derive(Debug, PartialEq)]
+pub enum BorrowedValue<'a> {
+    Null,
+    Bool(bool),
+    String(Cow<'a, str>),
+    Integer(i64),
+    Number(f64),
+    RawNumber(&'a str),
+    Array(Vec<BorrowedValue<'a>>),
+    Object(Vec<(Cow<'a, str>, BorrowedValue<'a>)>),
+}
+
+impl BorrowedValue<'_> {
+    /// Copies any borrowed strings to produce an owned [`Value`].
+    pub fn into_owned(self) -> Value {
+        match self {
+            BorrowedValue::Null => Value::Null,
+            BorrowedValue::Bool(value) => Value::Bool(value),
+            BorrowedValue::String(string) => Value::String(string.into_owned()),
+            BorrowedValue::Integer(integer) => Value::Integer(integer),
+            BorrowedValue::Number(number) => Value::Number(number),
+            BorrowedValue::RawNumber(raw) => Value::RawNumber(raw.to_owned()),
+            BorrowedValue::Array(array) => {
+                Value::Array(array.into_iter().map(BorrowedValue::into_owned).collect())
+            }
+            BorrowedValue::Object(key_values) => Value::Object(
+                key_values
+                    .into_iter()
+                    .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct BorrowedBuilder;
+
+impl<'a> Builder<'a> for BorrowedBuilder {
+    type Value = BorrowedValue<'a>;
+    type Array = Vec<BorrowedValue<'a>>;
+    type Object = Vec<(Cow<'a, str>, BorrowedValue<'a>)>;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> BorrowedValue<'a> {
+        match scalar {
+            Scalar::Null => BorrowedValue::Null,
+            Scalar::Bool(value) => BorrowedValue::Bool(value),
+            Scalar::String(string) => BorrowedValue::String(string),
+            Scalar::Integer(integer) => BorrowedValue::Integer(integer),
+            Scalar::Number(number) => BorrowedValue::Number(number),
+            Scalar::RawNumber(raw) => BorrowedValue::RawNumber(raw),
+        }
+    }
+
+    fn array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: BorrowedValue<'a>) {
+        array.push(value);
+    }
+
+    fn finish_array(&mut self, array: Self::Array) -> BorrowedValue<'a> {
+        BorrowedValue::Array(array)
+    }
+
+    fn object(&mut self) -> Self::Object {
+        Vec::new()
+    }
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: BorrowedValue<'a>) {
+        object.push((key, value));
+    }
+
+    fn finish_object(&mut self, object: Self::Object) -> BorrowedValue<'a> {
+        BorrowedValue::Object(object)
+    }
+}
+
+/// Parses a complete JSON document without copying escape-free strings.
+pub fn parse_borrowed(string: &str) -> Result<BorrowedValue<'_>, ParseError> {
+    Parser::new(string, ParserOptions::default()).parse(&mut BorrowedBuilder)
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use super::{parse_borrowed, BorrowedValue};
+    use crate::parse;
+
+    #[allow(clippy::ptr_arg)]
+    fn is_borrowed_from(string: &Cow<'_, str>, input: &str) -> bool {
+        match string {
+            Cow::Borrowed(string) => input.as_bytes().as_ptr_range().contains(&string.as_ptr()),
+            Cow::Owned(_) => false,
+        }
+    }
+
+    #[test]
+    fn test_parse_borrowed() {
+        let input =
+            "{\"plain\": [\"text\", 1, 2.5, true, false, null], \"esc\\u0061ped\": \"a\\nb\"}";
+        let value = parse_borrowed(input).unwrap();
+
+        let BorrowedValue::Object(key_values) = &value else {
+            panic!("expected an object, got {:?}", value);
+        };
+        let (plain, elements) = &key_values[0];
+        let (escaped, string) = &key_values[1];
+
+        assert!(is_borrowed_from(plain, input));
+        assert_eq!(plain, "plain");
+        match elements {
+            BorrowedValue::Array(array) => match &array[0] {
+                BorrowedValue::String(text) => {
+                    assert!(is_borrowed_from(text, input));
+                    assert_eq!(text, "text");
+                }
+                other => panic!("expected a string, got {:?}", other),
+            },
+            other => panic!("expected an array, got {:?}", other),
+        }
+
+        assert!(matches!(escaped, Cow::Owned(_)));
+        assert_eq!(escaped, "escaped");
+        assert_eq!(
+            string,
+            &BorrowedValue::String(Cow::Owned("a\nb".to_owned()))
+        );
+
+        assert_eq!(Ok(value.into_owned()), parse(input));
+    }
+
+    #[test]
+    fn test_parse_borrowed_errors() {
+        for input in ["", "[1,]", "\"abc", "\"a\\x\"", "{\"a\" 1}"] {
+            assert_eq!(parse_borrowed(input).err(), parse(input).err(), "{}", input);
+        }
+    }
+}
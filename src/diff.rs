@@ -0,0 +1,169 @@
+use crate::Value;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// One difference found by [`Value::diff`].
+///
+/// Paths are [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901) JSON
+/// Pointers, so they can be looked up with [`Value::pointer`].
+#[derive(Debug, PartialEq)]
+pub enum Change<'a> {
+    /// `value` is present only in the new document.
+    Added { path: String, value: &'a Value },
+    /// The value at `path` is present only in the old document.
+    Removed { path: String },
+    /// The value at `path` changed from `from` to `to`.
+    Modified {
+        path: String,
+        from: &'a Value,
+        to: &'a Value,
+    },
+}
+
+impl Value {
+    /// Lists the changes that turn `self` into `other`.
+    ///
+    /// Objects are compared member by member, using the first of any
+    /// duplicate keys as [`get`](Value::get) does, and arrays element by
+    /// element at the same index. Any other difference, including a change of
+    /// type, is reported as one [`Change::Modified`] for the whole value.
+    pub fn diff<'a>(&'a self, other: &'a Value) -> Vec<Change<'a>> {
+        let mut changes = Vec::new();
+        diff_into(self, other, &mut String::new(), &mut changes);
+        changes
+    }
+}
+
+fn diff_into<'a>(old: &'a Value, new: &'a Value, path: &mut String, changes: &mut Vec<Change<'a>>) {
+    match (old, new) {
+        (Value::Object(old_members), Value::Object(new_members)) => {
+            for (i, (key, value)) in old_members.iter().enumerate() {
+                if old_members[..i].iter().any(|(k, _)| k == key) {
+                    continue;
+                }
+                with_segment(path, key, |path| match new.get(key) {
+                    Some(other) => diff_into(value, other, path, changes),
+                    None => changes.push(Change::Removed { path: path.clone() }),
+                });
+            }
+            for (i, (key, value)) in new_members.iter().enumerate() {
+                if old.get(key).is_none() && !new_members[..i].iter().any(|(k, _)| k == key) {
+                    with_segment(path, key, |path| {
+                        changes.push(Change::Added {
+                            path: path.clone(),
+                            value,
+                        })
+                    });
+                }
+            }
+        }
+        (Value::Array(old_elements), Value::Array(new_elements)) => {
+            for (i, value) in old_elements.iter().enumerate() {
+                with_segment(path, &i.to_string(), |path| match new_elements.get(i) {
+                    Some(other) => diff_into(value, other, path, changes),
+                    None => changes.push(Change::Removed { path: path.clone() }),
+                });
+            }
+            for (i, value) in new_elements.iter().enumerate().skip(old_elements.len()) {
+                with_segment(path, &i.to_string(), |path| {
+                    changes.push(Change::Added {
+                        path: path.clone(),
+                        value,
+                    })
+                });
+            }
+        }
+        _ if old != new => changes.push(Change::Modified {
+            path: path.clone(),
+            from: old,
+            to: new,
+        }),
+        _ => {}
+    }
+}
+
+/// Runs `f` with `segment` appended to `path` as an escaped pointer token.
+fn with_segment(path: &mut String, segment: &str, f: impl FnOnce(&mut String)) {
+    let len = path.len();
+    path.push('/');
+    path.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+    f(path);
+    path.truncate(len);
+}
+
+#[cfg(test)]
+mod test {
+    use super::Change;
+    use crate::{parse, Value};
+
+    #[test]
+    fn test_diff() {
+        let old = parse(
+            "{\"name\": \"ada\", \"tags\": [\"a\", \"b\"], \"meta\": {\"age\": 36, \"city\": \"London\"}, \"a/b\": 1}",
+        )
+        .unwrap();
+        let new = parse(
+            "{\"name\": \"ada\", \"tags\": [\"a\", \"c\", \"d\"], \"meta\": {\"age\": 37, \"langs\": []}, \"a/b\": {}}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            old.diff(&new),
+            [
+                Change::Modified {
+                    path: "/tags/1".to_owned(),
+                    from: &Value::from("b"),
+                    to: &Value::from("c"),
+                },
+                Change::Added {
+                    path: "/tags/2".to_owned(),
+                    value: &Value::from("d"),
+                },
+                Change::Modified {
+                    path: "/meta/age".to_owned(),
+                    from: &Value::Integer(36),
+                    to: &Value::Integer(37),
+                },
+                Change::Removed {
+                    path: "/meta/city".to_owned(),
+                },
+                Change::Added {
+                    path: "/meta/langs".to_owned(),
+                    value: &Value::Array(Vec::new()),
+                },
+                Change::Modified {
+                    path: "/a~1b".to_owned(),
+                    from: &Value::Integer(1),
+                    to: &Value::Object(Vec::new()),
+                },
+            ]
+        );
+        for change in old.diff(&new) {
+            if let Change::Added { path, value } = change {
+                assert_eq!(new.pointer(&path), Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_diff_equal() {
+        let value = parse("{\"a\": [1, {\"b\": null}], \"a\": 2}").unwrap();
+
+        assert_eq!(value.diff(&value), []);
+        assert_eq!(
+            parse("[1, 2]").unwrap().diff(&parse("[1]").unwrap()),
+            [Change::Removed {
+                path: "/1".to_owned()
+            }]
+        );
+        assert_eq!(
+            Value::Null.diff(&Value::Bool(false)),
+            [Change::Modified {
+                path: String::new(),
+                from: &Value::Null,
+                to: &Value::Bool(false),
+            }]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::string::String;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// What went wrong while parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    /// A char that cannot appear at this point in the document.
+    UnexpectedToken(char),
+    /// A char other than what must come next, such as the `:` after an
+    /// object key, or a value where a stray comma was found.
+    Expected {
+        /// What was required, e.g. `':'`, `',' or ']'` or `value`.
+        expected: &'static str,
+        found: char,
+    },
+    /// The input ended before the document was complete.
+    UnexpectedEof,
+    /// The input held nothing but whitespace, or comments where allowed.
+    EmptyInput,
+    /// The input ended partway through `null`, `true` or `false`, which is
+    /// held here.
+    TruncatedLiteral(&'static str),
+    /// A number literal that could not be converted to a number.
+    InvalidNumber,
+    /// A backslash followed by a char that does not form an escape.
+    InvalidEscape(char),
+    /// A `\u` escape that is malformed or does not encode a valid char.
+    InvalidUnicode,
+    /// An object key that was already used in the same object.
+    DuplicateKey(String),
+    /// Arrays and objects nested deeper than the configured maximum.
+    DepthLimitExceeded,
+    /// Input bytes that are not valid UTF-8.
+    InvalidUtf8,
+    /// A string with no closing quote; the error points at the opening one.
+    UnterminatedString,
+    /// A `/*` comment with no closing `*/`.
+    UnterminatedComment,
+    /// The input is longer than the configured maximum.
+    InputTooLarge,
+    /// A number literal longer than the configured maximum.
+    NumberTooLong,
+    /// A document holding more values than the configured maximum.
+    TooManyValues,
+    /// A string longer than the configured maximum; the error points at its
+    /// opening quote.
+    StringTooLong,
+    /// A top-level value other than an array or object, when
+    /// [`ParserOptions::require_container`](crate::ParserOptions::require_container)
+    /// is set.
+    ScalarRoot,
+    /// Reading the input failed.
+    #[cfg(feature = "std")]
+    Io(io::ErrorKind),
+}
+
+/// An error produced while parsing, along with where it occurred.
+///
+/// `offset` is the byte index into the input; `line` and `column` are the
+/// 1-based equivalent, with columns counted in chars.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    /// Where in the document's structure the error occurred, such as
+    /// `$.users[2].name`. Only recorded when
+    /// [`ParserOptions::error_paths`](crate::ParserOptions::error_paths) is
+    /// set.
+    pub path: Option<String>,
+    /// Set on errors from [`parse_ndjson`](crate::parse_ndjson), where
+    /// input ending early means one line did, so the position is always
+    /// shown, even for errors that usually concern the input as a whole.
+    pub line_delimited: bool,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::UnexpectedToken(ch) => write!(f, "Unexpected token '{}'", ch)?,
+            ErrorKind::Expected { expected, found } => {
+                write!(f, "Expected {} but found '{}'", expected, found)?
+            }
+            ErrorKind::UnexpectedEof => write!(f, "Unexpected end of input")?,
+            ErrorKind::EmptyInput => write!(f, "Empty input")?,
+            ErrorKind::TruncatedLiteral(literal) => {
+                write!(f, "Expected '{}' but input ended", literal)?
+            }
+            ErrorKind::InvalidNumber => write!(f, "Invalid number")?,
+            ErrorKind::InvalidEscape(ch) => write!(f, "Invalid escape '\\{}'", ch)?,
+            ErrorKind::InvalidUnicode => write!(f, "Invalid unicode escape")?,
+            ErrorKind::DuplicateKey(key) => write!(f, "Duplicate key '{}'", key)?,
+            ErrorKind::DepthLimitExceeded => write!(f, "Nesting depth limit exceeded")?,
+            ErrorKind::InvalidUtf8 => write!(f, "Invalid UTF-8")?,
+            ErrorKind::UnterminatedString => write!(f, "Unterminated string starting")?,
+            ErrorKind::UnterminatedComment => write!(f, "Unterminated comment")?,
+            ErrorKind::InputTooLarge => write!(f, "Input exceeds the maximum length")?,
+            ErrorKind::NumberTooLong => write!(f, "Number too long")?,
+            ErrorKind::TooManyValues => write!(f, "Too many values")?,
+            ErrorKind::StringTooLong => write!(f, "String too long")?,
+            ErrorKind::ScalarRoot => write!(f, "Top-level value must be an array or object")?,
+            #[cfg(feature = "std")]
+            ErrorKind::Io(kind) => write!(f, "Failed to read input: {}", kind)?,
+        }
+
+        // These concern the input as a whole rather than a point within it.
+        let whole_input = match self.kind {
+            ErrorKind::UnexpectedEof
+            | ErrorKind::EmptyInput
+            | ErrorKind::TruncatedLiteral(_)
+            | ErrorKind::InputTooLarge => true,
+            #[cfg(feature = "std")]
+            ErrorKind::Io(_) => true,
+            _ => false,
+        };
+        if !whole_input || self.line_delimited {
+            write!(f, " at line {}, column {}", self.line, self.column)?;
+        }
+
+        match &self.path {
+            Some(path) => write!(f, " (at {})", path),
+            None => Ok(()),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
@@ -0,0 +1,179 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Value;
+
+/// Types that can be extracted from a [`Value`], for typed access without
+/// pulling in serde.
+///
+/// Implemented for `bool`, `i64`, `f64`, `String`, and for `Vec<T>` and
+/// `Option<T>` of any of them, where `null` converts to `None`.
+pub trait FromJson: Sized {
+    fn from_json(value: &Value) -> Result<Self, ConversionError>;
+}
+
+/// The error from [`FromJson::from_json`] when a value does not have the
+/// type asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    /// What was asked for, such as `"string"`.
+    pub expected: &'static str,
+    /// The [type name](Value::type_name) of what was found instead.
+    pub found: &'static str,
+    /// The JSON Pointer, relative to the value being converted, of the value
+    /// that could not be, such as `/2` for the third element of an array.
+    pub path: String,
+}
+
+impl ConversionError {
+    fn new(expected: &'static str, value: &Value) -> Self {
+        ConversionError {
+            expected,
+            found: value.type_name(),
+            path: String::new(),
+        }
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)?;
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ConversionError {}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> Result<Self, ConversionError> {
+        value
+            .as_bool()
+            .ok_or_else(|| ConversionError::new("boolean", value))
+    }
+}
+
+/// Accepts only numbers written as integers.
+impl FromJson for i64 {
+    fn from_json(value: &Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Integer(integer) => Ok(*integer),
+            Value::RawNumber(raw) => raw
+                .parse()
+                .map_err(|_| ConversionError::new("integer", value)),
+            _ => Err(ConversionError::new("integer", value)),
+        }
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &Value) -> Result<Self, ConversionError> {
+        value
+            .as_f64()
+            .ok_or_else(|| ConversionError::new("number", value))
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &Value) -> Result<Self, ConversionError> {
+        value
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| ConversionError::new("string", value))
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &Value) -> Result<Self, ConversionError> {
+        let array = value
+            .as_array()
+            .ok_or_else(|| ConversionError::new("array", value))?;
+
+        array
+            .iter()
+            .enumerate()
+            .map(|(i, element)| {
+                T::from_json(element).map_err(|mut error| {
+                    error.path = format!("/{}{}", i, error.path);
+                    error
+                })
+            })
+            .collect()
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &Value) -> Result<Self, ConversionError> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_json(value).map(Some),
+        }
+    }
+}
+
+impl Value {
+    /// Converts the value to `T`, as [`FromJson::from_json`] does.
+    pub fn to<T: FromJson>(&self) -> Result<T, ConversionError> {
+        T::from_json(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConversionError, FromJson};
+    use crate::parse;
+
+    #[test]
+    fn test_from_json() {
+        let value = parse("{\"scores\": [1, 2.5, -3e2], \"name\": null, \"ok\": true}").unwrap();
+
+        assert_eq!(
+            value["scores"].to::<Vec<f64>>(),
+            Ok(Vec::from([1.0, 2.5, -300.0]))
+        );
+        assert_eq!(Option::<String>::from_json(&value["name"]), Ok(None));
+        assert_eq!(
+            parse("\"ada\"").unwrap().to::<Option<String>>(),
+            Ok(Some("ada".to_owned()))
+        );
+        assert_eq!(bool::from_json(&value["ok"]), Ok(true));
+        assert_eq!(
+            parse("[[1], [], null]")
+                .unwrap()
+                .to::<Vec<Option<Vec<i64>>>>(),
+            Ok(Vec::from([Some(Vec::from([1])), Some(Vec::new()), None]))
+        );
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        let error = parse("[[1, 2], [3, \"4\"]]")
+            .unwrap()
+            .to::<Vec<Vec<f64>>>()
+            .unwrap_err();
+        assert_eq!(
+            error,
+            ConversionError {
+                expected: "number",
+                found: "string",
+                path: "/1/1".to_owned(),
+            }
+        );
+        assert_eq!(error.to_string(), "expected number, found string at /1/1");
+
+        assert_eq!(
+            i64::from_json(&parse("1.5").unwrap())
+                .unwrap_err()
+                .to_string(),
+            "expected integer, found number"
+        );
+        assert_eq!(
+            String::from_json(&parse("{}").unwrap()).map_err(|error| error.found),
+            Err("object")
+        );
+    }
+}
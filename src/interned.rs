@@ -0,0 +1,235 @@
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::parser::{Builder, Parser, Scalar};
+use crate::{ParseError, ParserOptions, Value};
+
+/// A parsed JSON value whose object keys are shared through a [`KeyTable`].
+///
+/// Every occurrence of a key, across all documents parsed with the same
+/// table, points at the same allocation, which saves memory on arrays of
+/// records that repeat the same handful of keys.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InternedValue {
+    Null,
+    Bool(bool),
+    String(String),
+    Integer(i64),
+    Number(f64),
+    RawNumber(String),
+    Array(Vec<InternedValue>),
+    Object(Vec<(Rc<str>, InternedValue)>),
+}
+
+impl InternedValue {
+    /// Copies every key to produce an owned [`Value`].
+    pub fn into_owned(self) -> Value {
+        match self {
+            InternedValue::Null => Value::Null,
+            InternedValue::Bool(value) => Value::Bool(value),
+            InternedValue::String(string) => Value::String(string),
+            InternedValue::Integer(integer) => Value::Integer(integer),
+            InternedValue::Number(number) => Value::Number(number),
+            InternedValue::RawNumber(raw) => Value::RawNumber(raw),
+            InternedValue::Array(array) => {
+                Value::Array(array.into_iter().map(InternedValue::into_owned).collect())
+            }
+            InternedValue::Object(key_values) => Value::Object(
+                key_values
+                    .into_iter()
+                    .map(|(key, value)| (String::from(&*key), value.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// The distinct object keys seen so far, which documents parsed through
+/// [`KeyTable::parse`] share.
+///
+/// The table only grows; drop it, or start a new one, to release keys that
+/// are no longer in use.
+///
+/// Interning is a method on the table rather than a field of
+/// [`ParserOptions`]: the table must be borrowed mutably for the whole parse,
+/// which a `Clone` options struct cannot hold, and a [`Value`] owns a
+/// separate `String` per key, so there is nothing for it to share. Plain
+/// [`parse`](crate::parse) and
+/// [`parse_with_options`](crate::parse_with_options) therefore never intern;
+/// use [`KeyTable::parse_with_options`] to combine interning with other
+/// options.
+#[derive(Debug, Default)]
+pub struct KeyTable {
+    keys: BTreeSet<Rc<str>>,
+}
+
+impl KeyTable {
+    pub fn new() -> Self {
+        KeyTable::default()
+    }
+
+    /// Parses a complete JSON document, interning its object keys in this
+    /// table.
+    pub fn parse(&mut self, string: &str) -> Result<InternedValue, ParseError> {
+        self.parse_with_options(string, ParserOptions::default())
+    }
+
+    /// Like [`parse`](KeyTable::parse), but using the given options.
+    ///
+    /// A [`number_sink`](ParserOptions::number_sink) produces a [`Value`],
+    /// which has nowhere to go here, so with one set numbers are kept as
+    /// [`RawNumber`](InternedValue::RawNumber), as if
+    /// [`raw_numbers`](ParserOptions::raw_numbers) were set instead.
+    pub fn parse_with_options(
+        &mut self,
+        string: &str,
+        options: ParserOptions,
+    ) -> Result<InternedValue, ParseError> {
+        Parser::new(string, options).parse(&mut InternedBuilder { table: self })
+    }
+
+    /// The number of distinct keys held.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn intern(&mut self, key: Cow<'_, str>) -> Rc<str> {
+        if let Some(existing) = self.keys.get(&*key) {
+            return existing.clone();
+        }
+
+        let key: Rc<str> = Rc::from(key);
+        self.keys.insert(key.clone());
+        key
+    }
+}
+
+struct InternedBuilder<'t> {
+    table: &'t mut KeyTable,
+}
+
+impl<'a> Builder<'a> for InternedBuilder<'_> {
+    type Value = InternedValue;
+    type Array = Vec<InternedValue>;
+    type Object = Vec<(Rc<str>, InternedValue)>;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> InternedValue {
+        match scalar {
+            Scalar::Null => InternedValue::Null,
+            Scalar::Bool(value) => InternedValue::Bool(value),
+            Scalar::String(string) => InternedValue::String(string.into_owned()),
+            Scalar::Integer(integer) => InternedValue::Integer(integer),
+            Scalar::Number(number) => InternedValue::Number(number),
+            Scalar::RawNumber(raw) => InternedValue::RawNumber(raw.to_owned()),
+        }
+    }
+
+    fn array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: InternedValue) {
+        array.push(value);
+    }
+
+    fn finish_array(&mut self, array: Self::Array) -> InternedValue {
+        InternedValue::Array(array)
+    }
+
+    fn object(&mut self) -> Self::Object {
+        Vec::new()
+    }
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: InternedValue) {
+        object.push((self.table.intern(key), value));
+    }
+
+    fn finish_object(&mut self, object: Self::Object) -> InternedValue {
+        InternedValue::Object(object)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::{InternedValue, KeyTable};
+    use crate::{parse, parse_with_options, ParserOptions};
+
+    fn keys(value: &InternedValue) -> Vec<Rc<str>> {
+        match value {
+            InternedValue::Array(array) => array.iter().flat_map(keys).collect(),
+            InternedValue::Object(key_values) => {
+                key_values.iter().map(|(key, _)| key.clone()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_key_table() {
+        let input = "[{\"id\": 1, \"name\": \"a\"}, {\"id\": 2, \"n\\u0061me\": \"b\"}]";
+        let mut table = KeyTable::new();
+        let value = table.parse(input).unwrap();
+
+        let keys = keys(&value);
+        assert_eq!(keys.len(), 4);
+        assert!(Rc::ptr_eq(&keys[0], &keys[2]));
+        assert!(Rc::ptr_eq(&keys[1], &keys[3]));
+        assert!(!Rc::ptr_eq(&keys[0], &keys[1]));
+        assert_eq!(table.len(), 2);
+
+        let other = table.parse("{\"name\": null, \"extra\": []}").unwrap();
+        match &other {
+            InternedValue::Object(key_values) => assert!(Rc::ptr_eq(&key_values[0].0, &keys[1])),
+            other => panic!("expected an object, got {:?}", other),
+        }
+        assert_eq!(table.len(), 3);
+
+        assert_eq!(Ok(value.into_owned()), parse(input));
+    }
+
+    #[test]
+    fn test_key_table_with_options() {
+        let mut table = KeyTable::new();
+        let options = ParserOptions {
+            raw_numbers: true,
+            ..ParserOptions::json5()
+        };
+        let value = table
+            .parse_with_options("[{\"a\": 1.50}, {\"a\": 2,},] // done", options)
+            .unwrap();
+
+        let keys = keys(&value);
+        assert!(Rc::ptr_eq(&keys[0], &keys[1]));
+        assert_eq!(table.len(), 1);
+        assert_eq!(value.into_owned().serialize(), "[{\"a\":1.50},{\"a\":2}]");
+
+        let strict = ParserOptions {
+            reject_duplicate_keys: true,
+            ..ParserOptions::default()
+        };
+        assert_eq!(
+            table
+                .parse_with_options("{\"a\": 1, \"a\": 2}", strict.clone())
+                .err(),
+            parse_with_options("{\"a\": 1, \"a\": 2}", strict).err()
+        );
+    }
+
+    #[test]
+    fn test_key_table_errors() {
+        let mut table = KeyTable::new();
+        for input in ["", "[1,]", "{\"a\" 1}"] {
+            assert_eq!(table.parse(input).err(), parse(input).err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! A small, dependency-free JSON parser.
+//!
+//! With the default `std` feature disabled the crate is `no_std`, needing
+//! only `alloc`; see that feature in `Cargo.toml` for what it adds.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "proptest")]
+mod arbitrary;
+mod borrowed;
+mod diff;
+mod error;
+mod from_json;
+mod interned;
+mod macros;
+#[cfg(feature = "std")]
+mod map;
+mod parser;
+mod patch;
+mod pointer;
+mod ser;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod spanned;
+mod stream;
+mod validate;
+mod value;
+mod visit;
+
+/// Paths used by [`json!`], which must work whether or not the calling
+/// crate links `std`.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::String;
+    pub use alloc::vec;
+}
+
+pub use borrowed::{parse_borrowed, BorrowedValue};
+pub use diff::Change;
+pub use error::{ErrorKind, ParseError};
+pub use from_json::{ConversionError, FromJson};
+pub use interned::{InternedValue, KeyTable};
+#[cfg(feature = "std")]
+pub use map::{parse_as_map, parse_as_map_with_options, MapValue};
+pub use parser::{
+    parse, parse_bytes, parse_json5, parse_lenient_collect, parse_lossy, parse_many, parse_ndjson,
+    parse_prefix, parse_with_options, unescape_string, Documents, NumberSink, ParseContext,
+    ParseResult, ParserOptions,
+};
+#[cfg(feature = "std")]
+pub use parser::{parse_framed, parse_reader};
+pub use patch::PatchError;
+pub use ser::{escape_string, SerializerOptions};
+#[cfg(feature = "std")]
+pub use ser::{to_writer, to_writer_pretty, write_array_stream};
+pub use spanned::{parse_spanned, Spanned, SpannedNode};
+pub use stream::{Event, StreamParser};
+pub use validate::validate;
+pub use value::{TypeError, Value};
+pub use visit::Visitor;
@@ -0,0 +1,107 @@
+/// Builds a [`Value`](crate::Value) from a JSON literal.
+///
+/// Arrays and objects nest as they would in JSON, and anything else is a Rust
+/// expression converted with [`From`], so variables can be interpolated:
+///
+/// ```
+/// use rjson::json;
+///
+/// let name = "rjson";
+/// let value = json!({ "name": name, "items": [1, 2, 3], "meta": null });
+///
+/// assert_eq!(value, rjson::parse(r#"{"name":"rjson","items":[1,2,3],"meta":null}"#).unwrap());
+/// ```
+#[macro_export]
+macro_rules! json {
+    // Array elements are collected one token at a time until a comma, so
+    // that elements may span several tokens.
+    (@array [$($elements:expr,)*] []) => {
+        $crate::__private::vec![$($elements,)*]
+    };
+    (@array [$($elements:expr,)*] [$($element:tt)+]) => {
+        $crate::__private::vec![$($elements,)* $crate::json!($($element)+)]
+    };
+    (@array [$($elements:expr,)*] [$($element:tt)+] , $($rest:tt)*) => {
+        $crate::json!(@array [$($elements,)* $crate::json!($($element)+),] [] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] [$($element:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::json!(@array [$($elements,)*] [$($element)* $next] $($rest)*)
+    };
+
+    // Object members are a single key token, a colon, and a value collected
+    // the same way as an array element.
+    (@object [$($members:expr,)*]) => {
+        $crate::__private::vec![$($members,)*]
+    };
+    (@object [$($members:expr,)*] $key:tt : $($rest:tt)*) => {
+        $crate::json!(@member [$($members,)*] $key [] $($rest)*)
+    };
+    (@member [$($members:expr,)*] $key:tt [$($value:tt)+]) => {
+        $crate::json!(@object [$($members,)* $crate::json!(@key $key $($value)+),])
+    };
+    (@member [$($members:expr,)*] $key:tt [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::json!(@object [$($members,)* $crate::json!(@key $key $($value)+),] $($rest)*)
+    };
+    (@member [$($members:expr,)*] $key:tt [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::json!(@member [$($members,)*] $key [$($value)* $next] $($rest)*)
+    };
+    (@key $key:tt $($value:tt)+) => {
+        ($crate::__private::String::from($key), $crate::json!($($value)+))
+    };
+
+    (null) => {
+        $crate::Value::Null
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::Value::Array($crate::json!(@array [] [] $($tt)*))
+    };
+    ({ $($tt:tt)* }) => {
+        $crate::Value::Object($crate::json!(@object [] $($tt)*))
+    };
+    ($other:expr) => {
+        $crate::Value::from($other)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse, Value};
+
+    #[test]
+    fn test_json() {
+        assert_eq!(json!(null), Value::Null);
+        assert_eq!(json!(true), Value::Bool(true));
+        assert_eq!(json!(-1), Value::Integer(-1));
+        assert_eq!(json!(2.5), Value::Number(2.5));
+        assert_eq!(json!("a"), Value::String("a".to_owned()));
+        assert_eq!(json!([]), Value::Array(Vec::new()));
+        assert_eq!(json!({}), Value::Object(Vec::new()));
+        assert_eq!(
+            json!([1, -2, null, "x", [true, [false]], {},]),
+            parse("[1, -2, null, \"x\", [true, [false]], {}]").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_interpolation() {
+        let name = "rjson";
+        let count = 3;
+        let items = Vec::from([json!(1), json!(2)]);
+
+        assert_eq!(
+            json!({
+                "name": name,
+                "count": count * 2 + 1,
+                "items": items,
+                "nested": { "empty": [], "null": null, "list": [1, { "a": "b" }] },
+                name: name.len() as i64,
+            }),
+            parse(
+                "{\"name\": \"rjson\", \"count\": 7, \"items\": [1, 2], \
+                 \"nested\": {\"empty\": [], \"null\": null, \"list\": [1, {\"a\": \"b\"}]}, \
+                 \"rjson\": 5}"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use crate::parser::{Builder, Parser, Scalar};
+use crate::{ParseError, ParserOptions};
+
+/// A parsed JSON value whose objects are hash maps, as returned by
+/// [`parse_as_map`].
+///
+/// Member order is lost, and of several members with the same key only the
+/// last is kept. In return, looking up a key takes constant time rather than
+/// a scan of the members.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapValue {
+    Null,
+    Bool(bool),
+    String(String),
+    Integer(i64),
+    Number(f64),
+    RawNumber(String),
+    Array(Vec<MapValue>),
+    Object(HashMap<String, MapValue>),
+}
+
+impl MapValue {
+    /// Looks up an object member like [`Value::get`](crate::Value::get).
+    pub fn get(&self, key: &str) -> Option<&MapValue> {
+        match self {
+            MapValue::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Looks up an array element like
+    /// [`Value::get_index`](crate::Value::get_index).
+    pub fn get_index(&self, i: usize) -> Option<&MapValue> {
+        match self {
+            MapValue::Array(array) => array.get(i),
+            _ => None,
+        }
+    }
+}
+
+struct MapBuilder;
+
+impl<'a> Builder<'a> for MapBuilder {
+    type Value = MapValue;
+    type Array = Vec<MapValue>;
+    type Object = HashMap<String, MapValue>;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> MapValue {
+        match scalar {
+            Scalar::Null => MapValue::Null,
+            Scalar::Bool(value) => MapValue::Bool(value),
+            Scalar::String(string) => MapValue::String(string.into_owned()),
+            Scalar::Integer(integer) => MapValue::Integer(integer),
+            Scalar::Number(number) => MapValue::Number(number),
+            Scalar::RawNumber(raw) => MapValue::RawNumber(raw.to_owned()),
+        }
+    }
+
+    fn array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: MapValue) {
+        array.push(value);
+    }
+
+    fn finish_array(&mut self, array: Self::Array) -> MapValue {
+        MapValue::Array(array)
+    }
+
+    fn object(&mut self) -> Self::Object {
+        HashMap::new()
+    }
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: MapValue) {
+        object.insert(key.into_owned(), value);
+    }
+
+    fn finish_object(&mut self, object: Self::Object) -> MapValue {
+        MapValue::Object(object)
+    }
+}
+
+/// Parses a complete JSON document straight into [`MapValue`]s, for callers
+/// that only look members up by key, such as when loading configuration.
+pub fn parse_as_map(string: &str) -> Result<MapValue, ParseError> {
+    parse_as_map_with_options(string, ParserOptions::default())
+}
+
+/// Like [`parse_as_map`], but using the given options.
+///
+/// With [`reject_duplicate_keys`](ParserOptions::reject_duplicate_keys) set,
+/// a repeated key is an error rather than replacing the earlier member. A
+/// [`number_sink`](ParserOptions::number_sink) produces a
+/// [`Value`](crate::Value), which has nowhere to go here, so with one set
+/// numbers are kept as [`RawNumber`](MapValue::RawNumber).
+pub fn parse_as_map_with_options(
+    string: &str,
+    options: ParserOptions,
+) -> Result<MapValue, ParseError> {
+    Parser::new(string, options).parse(&mut MapBuilder)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{parse_as_map, parse_as_map_with_options, MapValue};
+    use crate::{ErrorKind, ParserOptions};
+
+    #[test]
+    fn test_parse_as_map() {
+        let value = parse_as_map("{\"a\": [1, {\"b\": null}], \"c\": 1, \"c\": \"two\"}").unwrap();
+
+        assert_eq!(
+            value.get("a").and_then(|a| a.get_index(1)),
+            Some(&MapValue::Object(HashMap::from([(
+                "b".to_owned(),
+                MapValue::Null
+            )])))
+        );
+        assert_eq!(value.get("c"), Some(&MapValue::String("two".to_owned())));
+        assert_eq!(value.get("d"), None);
+        match &value {
+            MapValue::Object(map) => assert_eq!(map.len(), 2),
+            other => panic!("expected an object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_as_map_lookup() {
+        let input = format!(
+            "{{{}}}",
+            (0..10_000)
+                .map(|i| format!("\"key{}\": {}", i, i))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let value = parse_as_map(&input).unwrap();
+
+        let MapValue::Object(map) = &value else {
+            panic!("expected an object, got {:?}", value);
+        };
+        assert_eq!(map.len(), 10_000);
+        assert_eq!(value.get("key9999"), Some(&MapValue::Integer(9999)));
+        assert_eq!(value.get("key10000"), None);
+    }
+
+    #[test]
+    fn test_parse_as_map_errors() {
+        // A repeated key would replace the earlier member, but a malformed
+        // value under it still fails the whole document.
+        let input = "{\"a\": {\"b\": 1}, \"a\": {\"b\": 2, \"b\" 3}}";
+        let error = parse_as_map(input).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ErrorKind::Expected {
+                expected: "':'",
+                found: '3'
+            }
+        );
+        assert_eq!((error.offset, error.column), (34, 35));
+
+        let paths = ParserOptions {
+            error_paths: true,
+            ..ParserOptions::default()
+        };
+        assert_eq!(
+            parse_as_map_with_options(input, paths)
+                .unwrap_err()
+                .path
+                .as_deref(),
+            Some("$.a")
+        );
+
+        let strict = ParserOptions {
+            reject_duplicate_keys: true,
+            ..ParserOptions::default()
+        };
+        assert_eq!(
+            parse_as_map_with_options("{\"a\": {\"b\": 1, \"b\": 2}}", strict.clone())
+                .unwrap_err()
+                .kind,
+            ErrorKind::DuplicateKey("b".to_owned())
+        );
+        assert!(parse_as_map_with_options("[{\"b\": 1}, {\"b\": 2}]", strict).is_ok());
+    }
+}
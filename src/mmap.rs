@@ -0,0 +1,95 @@
+//! A read-only memory map of a whole file, so that huge inputs can be parsed
+//! without first being copied into a `String`.
+//!
+//! The mapped pages still count towards the process's resident memory while
+//! they are touched, but they are clean pages backed by the file, which the
+//! kernel can drop under memory pressure instead of swapping them out. Like
+//! any memory map, the contents are undefined if the file is modified while
+//! it is mapped.
+
+use std::ffi::{c_int, c_void};
+use std::fs::File;
+use std::io;
+use std::ops::Deref;
+use std::os::unix::io::AsRawFd;
+use std::ptr;
+use std::slice;
+
+const PROT_READ: c_int = 1;
+const MAP_PRIVATE: c_int = 2;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        len: usize,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: i64,
+    ) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+pub struct Mmap {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Mmap {
+    /// Maps the whole of `file` for reading.
+    pub fn open(file: &File) -> io::Result<Mmap> {
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::OutOfMemory, "file too large to map"))?;
+
+        // Mapping zero bytes is an error, but there is nothing to map anyway.
+        if len == 0 {
+            return Ok(Mmap {
+                ptr: ptr::null_mut(),
+                len,
+            });
+        }
+
+        // SAFETY: the kernel picks the address, and the mapping is private
+        // and read-only, so it aliases nothing else in this process.
+        let ptr = unsafe {
+            mmap(
+                ptr::null_mut(),
+                len,
+                PROT_READ,
+                MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Mmap { ptr, len })
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+
+        // SAFETY: `ptr` points to `len` readable bytes until `drop` unmaps
+        // them.
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            // SAFETY: this is the mapping made in `open`, and no borrow of it
+            // can outlive `self`.
+            unsafe { munmap(self.ptr, self.len) };
+        }
+    }
+}
@@ -0,0 +1,2751 @@
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::Peekable;
+use core::ops::Range;
+use core::str::CharIndices;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use crate::{ErrorKind, ParseError, Value};
+
+pub type ParseResult = core::result::Result<Value, ParseError>;
+
+/// Settings that alter how strictly a document is parsed.
+///
+/// The default matches [`parse`].
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    /// Reject objects that contain the same key more than once, rather than
+    /// keeping every member.
+    pub reject_duplicate_keys: bool,
+    /// The deepest that arrays and objects may be nested before parsing
+    /// fails. Parsing recurses once per level, so this bounds stack usage.
+    /// Defaults to 128.
+    pub max_depth: usize,
+    /// Treat `//` line comments and `/* */` block comments as whitespace.
+    pub allow_comments: bool,
+    /// Accept a comma after the last element of an array or object.
+    pub allow_trailing_commas: bool,
+    /// The longest input, in bytes, that will be parsed at all. Defaults to
+    /// no limit.
+    pub max_input_length: usize,
+    /// The longest a single number literal may be, in bytes, before parsing
+    /// fails rather than handing it to the float conversion. Defaults to
+    /// 1024.
+    pub max_number_length: usize,
+    /// Keep each number literal exactly as written, as a
+    /// [`Value::RawNumber`], rather than converting it. This avoids any loss
+    /// of precision, leaving interpretation to the caller. The tokens
+    /// accepted by [`allow_non_finite`](ParserOptions::allow_non_finite) are
+    /// not number literals and still become a [`Value::Number`], so that the
+    /// serializer can write them as `null` rather than as invalid JSON.
+    pub raw_numbers: bool,
+    /// Stop after the first complete value and ignore whatever follows it,
+    /// rather than rejecting trailing content.
+    pub allow_trailing: bool,
+    /// Record in [`ParseError::path`] which array elements and object
+    /// members the parser was inside when it failed.
+    pub error_paths: bool,
+    /// Reject a document whose top-level value is not an array or object,
+    /// as JSON did before RFC 7159.
+    pub require_container: bool,
+    /// Hand every number literal to this sink to convert, rather than
+    /// converting it to an `i64` or `f64`. Takes precedence over
+    /// [`raw_numbers`](ParserOptions::raw_numbers). As there, `NaN`,
+    /// `Infinity` and `-Infinity` are not handed to the sink but always
+    /// become a [`Value::Number`].
+    pub number_sink: Option<Arc<dyn NumberSink>>,
+    /// Accept `null`, `true` and `false` spelled in any mix of upper and
+    /// lower case, such as `True` or `NULL`, as some non-conforming
+    /// producers emit.
+    pub case_insensitive_literals: bool,
+    /// The most values, counting every scalar, array and object at any
+    /// depth, that a document may hold before parsing fails. This bounds the
+    /// memory a flat but huge document can take, which the depth limit does
+    /// not. Defaults to no limit.
+    pub max_values: usize,
+    /// Accept the bare tokens `NaN`, `Infinity` and `-Infinity` that some
+    /// JavaScript-derived producers emit, as the corresponding
+    /// [`Value::Number`], even with
+    /// [`raw_numbers`](ParserOptions::raw_numbers) or
+    /// [`number_sink`](ParserOptions::number_sink) set. JSON cannot express
+    /// these, so the serializer writes them back out as `null`.
+    pub allow_non_finite: bool,
+    /// The most chars a string, or an object key, may hold once its escapes
+    /// are decoded, before parsing fails. Defaults to no limit.
+    pub max_string_length: usize,
+    /// Also treat vertical tab, form feed, no-break space, U+2028, U+2029
+    /// and the byte order mark as whitespace between tokens, as JSON5 does.
+    /// Strict JSON allows only space, tab, CR and LF.
+    pub allow_extended_whitespace: bool,
+    /// Decode a `\\u` escape of a surrogate that is not part of a pair as
+    /// U+FFFD REPLACEMENT CHARACTER, rather than failing.
+    pub replace_lone_surrogates: bool,
+}
+
+/// Converts number literals into values, for applications that need a
+/// representation other than `f64`, such as an exact decimal type.
+///
+/// Set through [`ParserOptions::number_sink`].
+pub trait NumberSink: Send + Sync {
+    /// Converts `literal`, which is exactly as written in the input and has
+    /// already been checked against the JSON number grammar.
+    fn number(&self, literal: &str) -> Value;
+}
+
+impl fmt::Debug for dyn NumberSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("NumberSink")
+    }
+}
+
+impl ParserOptions {
+    /// Options for the lenient, JSON5-inspired dialect used by
+    /// [`parse_json5`].
+    pub fn json5() -> Self {
+        ParserOptions {
+            allow_comments: true,
+            allow_trailing_commas: true,
+            allow_non_finite: true,
+            allow_extended_whitespace: true,
+            ..ParserOptions::default()
+        }
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            reject_duplicate_keys: false,
+            max_depth: 128,
+            allow_comments: false,
+            allow_trailing_commas: false,
+            max_input_length: usize::MAX,
+            max_number_length: 1024,
+            raw_numbers: false,
+            allow_trailing: false,
+            error_paths: false,
+            require_container: false,
+            number_sink: None,
+            case_insensitive_literals: false,
+            max_values: usize::MAX,
+            allow_non_finite: false,
+            max_string_length: usize::MAX,
+            allow_extended_whitespace: false,
+            replace_lone_surrogates: false,
+        }
+    }
+}
+
+/// A value other than an array or object, as handed to a [`Builder`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum Scalar<'a> {
+    Null,
+    Bool(bool),
+    String(Cow<'a, str>),
+    Integer(i64),
+    Number(f64),
+    RawNumber(&'a str),
+}
+
+/// Assembles the output of a parse from the values the parser encounters.
+///
+/// The parser only drives the grammar; what gets built, and whether anything
+/// is allocated at all, is up to the builder.
+pub(crate) trait Builder<'a> {
+    type Value;
+    type Array;
+    type Object;
+
+    /// Whether strings are unescaped before being handed to the builder.
+    /// When false, escapes are still checked but strings are passed through
+    /// raw, so that nothing needs to be allocated for them.
+    const DECODE_STRINGS: bool = true;
+
+    /// Whether [`span`](Builder::span) should be called with the byte range
+    /// of each value once it is built.
+    const RECORD_SPANS: bool = false;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> Self::Value;
+
+    fn array(&mut self) -> Self::Array;
+
+    fn push(&mut self, array: &mut Self::Array, value: Self::Value);
+
+    fn finish_array(&mut self, array: Self::Array) -> Self::Value;
+
+    fn object(&mut self) -> Self::Object;
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: Self::Value);
+
+    fn finish_object(&mut self, object: Self::Object) -> Self::Value;
+
+    fn span(&mut self, value: Self::Value, _span: Range<usize>) -> Self::Value {
+        value
+    }
+}
+
+/// Builds an owned [`Value`].
+pub(crate) struct ValueBuilder;
+
+impl<'a> Builder<'a> for ValueBuilder {
+    type Value = Value;
+    type Array = Vec<Value>;
+    type Object = Vec<(String, Value)>;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> Value {
+        match scalar {
+            Scalar::Null => Value::Null,
+            Scalar::Bool(value) => Value::Bool(value),
+            Scalar::String(string) => Value::String(string.into_owned()),
+            Scalar::Integer(integer) => Value::Integer(integer),
+            Scalar::Number(number) => Value::Number(number),
+            Scalar::RawNumber(raw) => Value::RawNumber(raw.to_owned()),
+        }
+    }
+
+    fn array(&mut self) -> Vec<Value> {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Vec<Value>, value: Value) {
+        array.push(value);
+    }
+
+    fn finish_array(&mut self, array: Vec<Value>) -> Value {
+        Value::Array(array)
+    }
+
+    fn object(&mut self) -> Vec<(String, Value)> {
+        Vec::new()
+    }
+
+    fn insert(&mut self, object: &mut Vec<(String, Value)>, key: Cow<'a, str>, value: Value) {
+        object.push((key.into_owned(), value));
+    }
+
+    fn finish_object(&mut self, object: Vec<(String, Value)>) -> Value {
+        Value::Object(object)
+    }
+}
+
+/// Builds an owned [`Value`] like [`ValueBuilder`], but converts numbers with
+/// a [`NumberSink`]. The parser must be keeping raw numbers for it.
+struct SinkBuilder<'s> {
+    sink: &'s dyn NumberSink,
+    values: ValueBuilder,
+}
+
+impl<'a> Builder<'a> for SinkBuilder<'_> {
+    type Value = Value;
+    type Array = <ValueBuilder as Builder<'a>>::Array;
+    type Object = <ValueBuilder as Builder<'a>>::Object;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> Value {
+        match scalar {
+            Scalar::RawNumber(raw) => self.sink.number(raw),
+            scalar => self.values.scalar(scalar),
+        }
+    }
+
+    fn array(&mut self) -> Self::Array {
+        self.values.array()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: Value) {
+        self.values.push(array, value)
+    }
+
+    fn finish_array(&mut self, array: Self::Array) -> Value {
+        self.values.finish_array(array)
+    }
+
+    fn object(&mut self) -> Self::Object {
+        self.values.object()
+    }
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: Value) {
+        self.values.insert(object, key, value)
+    }
+
+    fn finish_object(&mut self, object: Self::Object) -> Value {
+        self.values.finish_object(object)
+    }
+}
+
+pub(crate) struct Parser<'a> {
+    source: &'a str,
+    pub(crate) chars: Peekable<CharIndices<'a>>,
+    options: ParserOptions,
+    depth: usize,
+    /// How many values have been started so far.
+    values: usize,
+    /// The errors recovered from so far, when collecting them rather than
+    /// stopping at the first.
+    errors: Option<Vec<ParseError>>,
+    /// Where strings with escapes are decoded before being copied out.
+    scratch: String,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(source: &'a str, options: ParserOptions) -> Self {
+        Parser {
+            source,
+            chars: source.char_indices().peekable(),
+            options,
+            depth: 0,
+            values: 0,
+            errors: None,
+            scratch: String::new(),
+        }
+    }
+
+    pub(crate) fn error_at(&self, kind: ErrorKind, offset: usize) -> ParseError {
+        error_at(self.source, kind, offset)
+    }
+
+    /// Fails with `Expected` when the next character is a comma where
+    /// `expected` belongs. A leading or doubled comma is the likeliest typo,
+    /// so say what was missing rather than only what was found.
+    pub(crate) fn reject_comma(
+        &mut self,
+        expected: &'static str,
+    ) -> core::result::Result<(), ParseError> {
+        match self.chars.peek() {
+            Some(&(index, ',')) => Err(self.error_at(
+                ErrorKind::Expected {
+                    expected,
+                    found: ',',
+                },
+                index,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn error<T>(
+        &self,
+        data: Option<(usize, char)>,
+    ) -> core::result::Result<T, ParseError> {
+        match data {
+            Some((index, ch)) => Err(self.error_at(ErrorKind::UnexpectedToken(ch), index)),
+            _ => Err(self.error_at(ErrorKind::UnexpectedEof, self.source.len())),
+        }
+    }
+
+    /// Returns the byte offset of the next char, or the input length at EOF.
+    pub(crate) fn offset(&mut self) -> usize {
+        match self.chars.peek() {
+            Some(&(index, _)) => index,
+            None => self.source.len(),
+        }
+    }
+
+    pub(crate) fn consume_whitespace(&mut self) -> core::result::Result<(), ParseError> {
+        loop {
+            match self.chars.peek() {
+                Some((_, ' ' | '\t' | '\r' | '\n')) => {
+                    self.chars.next();
+                }
+                Some((
+                    _,
+                    '\u{000B}' | '\u{000C}' | '\u{00A0}' | '\u{2028}' | '\u{2029}' | '\u{FEFF}',
+                )) if self.options.allow_extended_whitespace => {
+                    self.chars.next();
+                }
+                Some(&(start, '/')) if self.options.allow_comments => {
+                    self.chars.next();
+                    self.consume_comment(start)?;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn consume_comment(&mut self, start: usize) -> core::result::Result<(), ParseError> {
+        match self.chars.next() {
+            Some((_, '/')) => {
+                for (_, ch) in self.chars.by_ref() {
+                    if ch == '\n' {
+                        break;
+                    }
+                }
+            }
+            Some((_, '*')) => {
+                let mut star = false;
+                loop {
+                    match self.chars.next() {
+                        Some((_, '/')) if star => break,
+                        Some((_, ch)) => star = ch == '*',
+                        None => return Err(self.error_at(ErrorKind::UnterminatedComment, start)),
+                    }
+                }
+            }
+            option => return self.error(option),
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn consume(&mut self, ch: char) -> bool {
+        match self.chars.peek() {
+            Some(&(_, next)) if next == ch => {
+                self.chars.next();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Consumes `ch`, or fails without consuming anything. `expected`
+    /// describes what should have come next for the error message.
+    pub(crate) fn try_consume(
+        &mut self,
+        ch: char,
+        expected: &'static str,
+    ) -> core::result::Result<(), ParseError> {
+        if self.consume(ch) {
+            return Ok(());
+        }
+
+        match self.chars.peek() {
+            Some(&(index, found)) => {
+                Err(self.error_at(ErrorKind::Expected { expected, found }, index))
+            }
+            None => self.error(None),
+        }
+    }
+
+    pub(crate) fn parse<B: Builder<'a>>(
+        &mut self,
+        builder: &mut B,
+    ) -> core::result::Result<B::Value, ParseError> {
+        let (value, offset) = self.parse_prefix(builder)?;
+
+        if offset == self.source.len() || self.options.allow_trailing {
+            Ok(value)
+        } else {
+            let data = self.chars.peek().copied();
+            self.error(data)
+        }
+    }
+
+    /// Parses one value and any whitespace after it, returning the value and
+    /// the offset of the first char that was not consumed.
+    pub(crate) fn parse_prefix<B: Builder<'a>>(
+        &mut self,
+        builder: &mut B,
+    ) -> core::result::Result<(B::Value, usize), ParseError> {
+        if self.source.len() > self.options.max_input_length {
+            return Err(self.error_at(ErrorKind::InputTooLarge, 0));
+        }
+
+        self.consume_whitespace()?;
+        match self.chars.peek() {
+            None => return Err(self.error_at(ErrorKind::EmptyInput, self.source.len())),
+            Some(&(start, ch)) if self.options.require_container && !matches!(ch, '[' | '{') => {
+                return Err(self.error_at(ErrorKind::ScalarRoot, start));
+            }
+            Some(_) => {}
+        }
+
+        let value = self
+            .parse_value(builder)
+            .map_err(|error| self.nest(error, || "$".to_owned()))?;
+
+        Ok((value, self.offset()))
+    }
+
+    fn parse_value<B: Builder<'a>>(
+        &mut self,
+        builder: &mut B,
+    ) -> core::result::Result<B::Value, ParseError> {
+        self.consume_whitespace()?;
+
+        // Leave a stray closing bracket for the enclosing container to see.
+        if let Some(&data @ (_, ']' | '}')) = self.chars.peek() {
+            return self.error(Some(data));
+        }
+
+        self.reject_comma("value")?;
+
+        let start = self.offset();
+        if self.values == self.options.max_values {
+            return Err(self.error_at(ErrorKind::TooManyValues, start));
+        }
+        self.values += 1;
+
+        let value = match self.chars.next() {
+            Some((_, ch @ ('[' | '{'))) => {
+                if self.depth == self.options.max_depth {
+                    return Err(self.error_at(ErrorKind::DepthLimitExceeded, start));
+                }
+
+                self.depth += 1;
+                let result = match ch {
+                    '[' => self.parse_array(builder),
+                    _ => self.parse_object(builder),
+                };
+                self.depth -= 1;
+
+                result?
+            }
+            data => {
+                let scalar = self.parse_scalar(data, B::DECODE_STRINGS)?;
+                builder.scalar(scalar)
+            }
+        };
+
+        let value = if B::RECORD_SPANS {
+            let end = self.offset();
+            builder.span(value, start..end)
+        } else {
+            value
+        };
+
+        self.consume_whitespace()?;
+
+        Ok(value)
+    }
+
+    /// Parses any value other than an array or object, given its first char.
+    pub(crate) fn parse_scalar(
+        &mut self,
+        data: Option<(usize, char)>,
+        decode: bool,
+    ) -> core::result::Result<Scalar<'a>, ParseError> {
+        if self.options.allow_non_finite {
+            match data {
+                // `NaN` rather than a differently cased `null`.
+                Some((_, 'N')) if matches!(self.chars.peek(), Some((_, 'a'))) => {
+                    return self.parse_literal("NaN", Scalar::Number(f64::NAN));
+                }
+                Some((_, 'I')) => {
+                    return self.parse_literal("Infinity", Scalar::Number(f64::INFINITY));
+                }
+                _ => (),
+            }
+        }
+
+        let data = match data {
+            Some((index, ch @ ('N' | 'T' | 'F'))) if self.options.case_insensitive_literals => {
+                Some((index, ch.to_ascii_lowercase()))
+            }
+            data => data,
+        };
+
+        match data {
+            Some((_, 'n')) => self.parse_literal("null", Scalar::Null),
+            Some((_, 't')) => self.parse_literal("true", Scalar::Bool(true)),
+            Some((_, 'f')) => self.parse_literal("false", Scalar::Bool(false)),
+            Some((_, '"')) => Ok(Scalar::String(self.parse_string(decode)?)),
+            Some((start, ch @ ('-' | '0'..='9'))) => self.parse_number(start, ch),
+            Some((start, '+')) => Err(self.error_at(ErrorKind::InvalidNumber, start)),
+            option => self.error(option),
+        }
+    }
+
+    fn parse_object<B: Builder<'a>>(
+        &mut self,
+        builder: &mut B,
+    ) -> core::result::Result<B::Value, ParseError> {
+        self.consume_whitespace()?;
+
+        let mut object = builder.object();
+        // Only tracked when duplicates are rejected, as the builder's object
+        // may not keep its keys around.
+        let mut keys = Vec::new();
+
+        if !self.consume('}') {
+            loop {
+                match self.parse_member(builder, &mut keys) {
+                    Ok((key, value)) => builder.insert(&mut object, key, value),
+                    Err(error) => self.recover(error)?,
+                }
+
+                if !self.parse_separator('}')? {
+                    break;
+                }
+
+                if self.options.allow_trailing_commas {
+                    self.consume_whitespace()?;
+                    if self.consume('}') {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(builder.finish_object(object))
+    }
+
+    /// Parses a `"key": value` pair. `keys` holds the keys seen so far in
+    /// the object, if duplicates are rejected.
+    fn parse_member<B: Builder<'a>>(
+        &mut self,
+        builder: &mut B,
+        keys: &mut Vec<Cow<'a, str>>,
+    ) -> core::result::Result<(Cow<'a, str>, B::Value), ParseError> {
+        self.consume_whitespace()?;
+
+        let key_start = self.offset();
+        if !self.consume('"') {
+            self.reject_comma("key")?;
+            let data = self.chars.peek().copied();
+            return self.error(data);
+        }
+
+        // Duplicates can only be spotted once keys are decoded.
+        let decode = B::DECODE_STRINGS || self.options.reject_duplicate_keys;
+        let key = self.parse_string(decode)?;
+        if self.options.reject_duplicate_keys {
+            if keys.contains(&key) {
+                let key = key.into_owned();
+                return Err(self.error_at(ErrorKind::DuplicateKey(key), key_start));
+            }
+
+            keys.push(key.clone());
+        }
+
+        self.consume_whitespace()?;
+
+        self.try_consume(':', "':'")?;
+
+        let value = self
+            .parse_value(builder)
+            .map_err(|error| self.nest(error, || key_segment(&key)))?;
+
+        Ok((key, value))
+    }
+
+    fn parse_array<B: Builder<'a>>(
+        &mut self,
+        builder: &mut B,
+    ) -> core::result::Result<B::Value, ParseError> {
+        let mut array = builder.array();
+        self.consume_whitespace()?;
+
+        if !self.consume(']') {
+            let mut index = 0;
+            loop {
+                match self.parse_value(builder) {
+                    Ok(value) => builder.push(&mut array, value),
+                    Err(error) => {
+                        let error = self.nest(error, || format!("[{}]", index));
+                        self.recover(error)?;
+                    }
+                }
+                index += 1;
+
+                if !self.parse_separator(']')? {
+                    break;
+                }
+
+                if self.options.allow_trailing_commas {
+                    self.consume_whitespace()?;
+                    if self.consume(']') {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(builder.finish_array(array))
+    }
+
+    /// Consumes the `,` or `close` that must follow an element, returning
+    /// whether another element follows.
+    ///
+    /// When collecting errors, anything else is skipped up to the next
+    /// separator, unless it closes an enclosing container instead.
+    fn parse_separator(&mut self, close: char) -> core::result::Result<bool, ParseError> {
+        loop {
+            if self.consume(',') {
+                return Ok(true);
+            }
+
+            let resync =
+                self.errors.is_some() && !matches!(self.chars.peek(), None | Some((_, ']' | '}')));
+            let expected = if close == ']' {
+                "',' or ']'"
+            } else {
+                "',' or '}'"
+            };
+            match self.try_consume(close, expected) {
+                Ok(()) => return Ok(false),
+                Err(error) if resync => self.recover(error)?,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Records `error` and skips to where parsing can resume, if errors are
+    /// being collected; otherwise hands the error back.
+    fn recover(&mut self, error: ParseError) -> core::result::Result<(), ParseError> {
+        match &mut self.errors {
+            Some(errors) => {
+                record(errors, error);
+                self.skip_to_separator();
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Skips to the next `,`, `]` or `}` that is not inside a string, array
+    /// or object, leaving it unconsumed.
+    fn skip_to_separator(&mut self) {
+        let mut depth = 0usize;
+
+        while let Some(&(_, ch)) = self.chars.peek() {
+            match ch {
+                ',' | ']' | '}' if depth == 0 => return,
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                '"' => {
+                    self.chars.next();
+                    while let Some((_, ch)) = self.chars.next() {
+                        match ch {
+                            '"' => break,
+                            '\\' => {
+                                self.chars.next();
+                            }
+                            _ => (),
+                        }
+                    }
+                    continue;
+                }
+                _ => (),
+            }
+
+            self.chars.next();
+        }
+    }
+
+    /// Prepends `segment` to the path of an error raised while parsing a
+    /// value within the current one, if paths are being recorded.
+    fn nest(&self, mut error: ParseError, segment: impl FnOnce() -> String) -> ParseError {
+        if self.options.error_paths {
+            let path = error.path.take().unwrap_or_default();
+            error.path = Some(segment() + &path);
+        }
+
+        error
+    }
+
+    fn skip_digits(&mut self) {
+        while let Some((_, '0'..='9')) = self.chars.peek() {
+            self.chars.next();
+        }
+    }
+
+    pub(crate) fn parse_number(
+        &mut self,
+        start: usize,
+        ch: char,
+    ) -> core::result::Result<Scalar<'a>, ParseError> {
+        match ch {
+            '0' if matches!(self.chars.peek(), Some((_, '0'..='9'))) => {
+                return Err(self.error_at(ErrorKind::InvalidNumber, start));
+            }
+            '0' => (),
+            '-' => match self.chars.next() {
+                Some((_, '0')) if matches!(self.chars.peek(), Some((_, '0'..='9'))) => {
+                    return Err(self.error_at(ErrorKind::InvalidNumber, start));
+                }
+                Some((_, '0'..='9')) => self.skip_digits(),
+                Some((_, 'I')) if self.options.allow_non_finite => {
+                    return self.parse_literal("Infinity", Scalar::Number(f64::NEG_INFINITY));
+                }
+                option => return self.error(option),
+            },
+            '1'..='9' => self.skip_digits(),
+            _ => return Err(self.error_at(ErrorKind::InvalidNumber, start)),
+        }
+
+        let mut is_integer = true;
+
+        if self.consume('.') {
+            is_integer = false;
+
+            if !matches!(self.chars.peek(), Some((_, '0'..='9'))) {
+                return Err(self.error_at(ErrorKind::InvalidNumber, start));
+            }
+
+            self.skip_digits();
+        }
+
+        if matches!(self.chars.peek(), Some((_, 'e' | 'E'))) {
+            is_integer = false;
+            self.chars.next();
+
+            if let Some((_, '+' | '-')) = self.chars.peek() {
+                self.chars.next();
+            }
+
+            match self.chars.next() {
+                Some((_, '0'..='9')) => self.skip_digits(),
+                option => return self.error(option),
+            }
+        }
+
+        // A number running straight into a letter or another `.`, as in
+        // `1abc` or `1.2.3`, is one malformed token rather than a number
+        // followed by junk.
+        if let Some(&(_, ch)) = self.chars.peek() {
+            if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+                return Err(self.error_at(ErrorKind::InvalidNumber, start));
+            }
+        }
+
+        let end = self.offset();
+        if end - start > self.options.max_number_length {
+            return Err(self.error_at(ErrorKind::NumberTooLong, start));
+        }
+
+        let num = &self.source[start..end];
+        if self.options.raw_numbers || self.options.number_sink.is_some() {
+            return Ok(Scalar::RawNumber(num));
+        }
+
+        // `-0` has no integer representation, so it stays a float to keep
+        // its sign. Integers too large for an `i64` fall back to a float too.
+        if is_integer && num != "-0" {
+            if let Ok(value) = num.parse::<i64>() {
+                return Ok(Scalar::Integer(value));
+            }
+        }
+
+        // Magnitudes too large for an `f64` parse as infinity, which has no
+        // JSON representation.
+        match num.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Scalar::Number(value)),
+            _ => Err(self.error_at(ErrorKind::InvalidNumber, start)),
+        }
+    }
+
+    /// Decodes a `\u` escape whose `u` is at `start`. A UTF-16 high surrogate
+    /// must be followed by a second escape holding the low surrogate, and
+    /// the pair decodes to a single char outside the Basic Multilingual
+    /// Plane.
+    fn parse_unicode(
+        &mut self,
+        start: usize,
+        string_start: usize,
+    ) -> core::result::Result<char, ParseError> {
+        let mut value = self.parse_hex4(string_start)?;
+
+        if (0xD800..0xDC00).contains(&value) {
+            // Leave whatever follows to be decoded on its own.
+            if self.options.replace_lone_surrogates && !self.low_surrogate_follows() {
+                return Ok(char::REPLACEMENT_CHARACTER);
+            }
+
+            if !self.consume('\\') || !self.consume('u') {
+                return match self.chars.peek() {
+                    Some(_) => Err(self.error_at(ErrorKind::InvalidUnicode, start)),
+                    None => Err(self.unterminated_string(string_start)),
+                };
+            }
+            let low = self.parse_hex4(string_start)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error_at(ErrorKind::InvalidUnicode, start));
+            }
+            value = 0x10000 + ((value - 0xD800) << 10) + (low - 0xDC00);
+        }
+
+        match char::from_u32(value) {
+            Some(ch) => Ok(ch),
+            None if self.options.replace_lone_surrogates => Ok(char::REPLACEMENT_CHARACTER),
+            None => Err(self.error_at(ErrorKind::InvalidUnicode, start)),
+        }
+    }
+
+    /// Whether the next six chars are a `\u` escape of a low surrogate,
+    /// without consuming them.
+    fn low_surrogate_follows(&self) -> bool {
+        let mut ahead = self.chars.clone().map(|(_, ch)| ch);
+        if ahead.next() != Some('\\') || ahead.next() != Some('u') {
+            return false;
+        }
+
+        let low = (0..4).try_fold(0, |value, _| Some(value * 16 + ahead.next()?.to_digit(16)?));
+        matches!(low, Some(0xDC00..=0xDFFF))
+    }
+
+    /// Reads the four hex digits of a `\u` escape.
+    fn parse_hex4(&mut self, string_start: usize) -> core::result::Result<u32, ParseError> {
+        let mut value = 0u32;
+
+        for i in (0..4).rev() {
+            value += 16u32.pow(i)
+                * match self.chars.next() {
+                    Some((_, '0')) => 0,
+                    Some((_, '1')) => 1,
+                    Some((_, '2')) => 2,
+                    Some((_, '3')) => 3,
+                    Some((_, '4')) => 4,
+                    Some((_, '5')) => 5,
+                    Some((_, '6')) => 6,
+                    Some((_, '7')) => 7,
+                    Some((_, '8')) => 8,
+                    Some((_, '9')) => 9,
+                    Some((_, 'a' | 'A')) => 10,
+                    Some((_, 'b' | 'B')) => 11,
+                    Some((_, 'c' | 'C')) => 12,
+                    Some((_, 'd' | 'D')) => 13,
+                    Some((_, 'e' | 'E')) => 14,
+                    Some((_, 'f' | 'F')) => 15,
+                    Some((index, _)) => return Err(self.error_at(ErrorKind::InvalidUnicode, index)),
+                    None => return Err(self.unterminated_string(string_start)),
+                }
+        }
+
+        Ok(value)
+    }
+
+    /// Parses the rest of a string whose opening quote was just consumed.
+    ///
+    /// Strings without escapes are borrowed straight from the source; only
+    /// once an escape is found is the string copied so it can be decoded.
+    /// Unless `decode` is set, escapes are only checked and the raw text
+    /// between the quotes is returned.
+    pub(crate) fn parse_string(
+        &mut self,
+        decode: bool,
+    ) -> core::result::Result<Cow<'a, str>, ParseError> {
+        let start = self.offset();
+        // Decoded chars so far, with each escape counting as one.
+        let mut len = 0;
+        loop {
+            match self.chars.next() {
+                Some((index, '"')) => return Ok(Cow::Borrowed(&self.source[start..index])),
+                Some((index, '\\')) if decode => {
+                    self.scratch.clear();
+                    self.scratch.push_str(&self.source[start..index]);
+                    break;
+                }
+                Some((_, '\\')) => {
+                    self.parse_escape(start)?;
+                }
+                Some(_) => (),
+                None => return Err(self.unterminated_string(start)),
+            }
+            len += 1;
+            self.check_string_length(len, start)?;
+        }
+
+        let ch = self.parse_escape(start)?;
+        self.scratch.push(ch);
+        len += 1;
+        self.check_string_length(len, start)?;
+
+        loop {
+            match self.chars.next() {
+                // Copying out allocates exactly the decoded length, while the
+                // scratch buffer keeps its capacity for the next string.
+                Some((_, '"')) => return Ok(Cow::Owned(self.scratch.clone())),
+                Some((_, '\\')) => {
+                    let ch = self.parse_escape(start)?;
+                    self.scratch.push(ch);
+                }
+                Some((_, ch)) => self.scratch.push(ch),
+                None => return Err(self.unterminated_string(start)),
+            }
+            len += 1;
+            self.check_string_length(len, start)?;
+        }
+    }
+
+    /// Fails once the string whose contents begin at `start` has grown past
+    /// the configured maximum, reporting it at the opening quote.
+    fn check_string_length(
+        &self,
+        len: usize,
+        start: usize,
+    ) -> core::result::Result<(), ParseError> {
+        if len > self.options.max_string_length {
+            return Err(self.error_at(ErrorKind::StringTooLong, start - 1));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the whole source as the contents of a string, without the
+    /// surrounding quotes.
+    fn unescape_fragment(&mut self) -> core::result::Result<String, ParseError> {
+        let mut out = String::with_capacity(self.source.len());
+        while let Some((index, ch)) = self.chars.next() {
+            match ch {
+                // An unescaped quote would end the string early.
+                '"' => return self.error(Some((index, ch))),
+                '\\' => match self.parse_escape(index + 1) {
+                    Ok(ch) => out.push(ch),
+                    // With no quotes of its own, the fragment is simply cut
+                    // short rather than unterminated.
+                    Err(ParseError {
+                        kind: ErrorKind::UnterminatedString,
+                        ..
+                    }) => return self.error(None),
+                    Err(error) => return Err(error),
+                },
+                ch => out.push(ch),
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Reports a string that runs to the end of the input at its opening
+    /// quote, which sits just before `start`.
+    fn unterminated_string(&self, start: usize) -> ParseError {
+        self.error_at(ErrorKind::UnterminatedString, start - 1)
+    }
+
+    /// Decodes the escape sequence following a backslash, in the string whose
+    /// contents begin at `string_start`. Input that ends partway through the
+    /// escape leaves the string unterminated.
+    fn parse_escape(&mut self, string_start: usize) -> core::result::Result<char, ParseError> {
+        match self.chars.next() {
+            Some((_, ch @ ('"' | '\\' | '/'))) => Ok(ch),
+            Some((_, 'n')) => Ok('\n'),
+            Some((_, 'b')) => Ok('\u{0008}'),
+            Some((_, 'r')) => Ok('\r'),
+            Some((_, 'f')) => Ok('\u{000C}'),
+            Some((_, 't')) => Ok('\t'),
+            Some((start, 'u')) => self.parse_unicode(start, string_start),
+            Some((index, ch)) => Err(self.error_at(ErrorKind::InvalidEscape(ch), index)),
+            None => Err(self.unterminated_string(string_start)),
+        }
+    }
+
+    /// Matches the rest of `literal`, whose first char was just consumed.
+    pub(crate) fn parse_literal(
+        &mut self,
+        literal: &'static str,
+        value: Scalar<'a>,
+    ) -> core::result::Result<Scalar<'a>, ParseError> {
+        let any_case = self.options.case_insensitive_literals;
+
+        for expected in literal.chars().skip(1) {
+            let matched = match self.chars.peek() {
+                Some(&(_, ch)) => {
+                    ch == expected || (any_case && ch.to_ascii_lowercase() == expected)
+                }
+                None => false,
+            };
+            if matched {
+                self.chars.next();
+            } else {
+                return match self.chars.peek().copied() {
+                    None => {
+                        Err(self.error_at(ErrorKind::TruncatedLiteral(literal), self.source.len()))
+                    }
+                    data => self.error(data),
+                };
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+/// Builds an error for the char at byte `offset` within `source`.
+///
+/// The 1-based line and column are only needed when reporting an error, so
+/// rather than counting newlines for every char consumed, the prefix of the
+/// source is rescanned on demand.
+fn error_at(source: &str, kind: ErrorKind, offset: usize) -> ParseError {
+    let consumed = &source[..offset];
+    let line = consumed.matches('\n').count() + 1;
+    let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+    let column = consumed[line_start..].chars().count() + 1;
+
+    ParseError {
+        kind,
+        offset,
+        line,
+        column,
+        path: None,
+        line_delimited: false,
+    }
+}
+
+/// Formats an object key as a path segment: `.key` when it is a plain
+/// identifier, or a quoted `["key"]` otherwise.
+fn key_segment(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+
+    if is_identifier {
+        format!(".{}", key)
+    } else {
+        format!("[{}]", Value::String(key.to_owned()).serialize())
+    }
+}
+
+/// Parses a complete JSON document.
+///
+/// Leading and trailing whitespace is allowed; anything else after the value
+/// is an error.
+pub fn parse(string: &str) -> ParseResult {
+    parse_with_options(string, ParserOptions::default())
+}
+
+/// Parses a single value from the start of `string`, ignoring whatever
+/// follows it.
+///
+/// Returns the value along with the byte offset just past it and any
+/// whitespace after it, which is where the next value would begin. This
+/// suits protocols that send several documents back to back.
+pub fn parse_prefix(string: &str) -> core::result::Result<(Value, usize), ParseError> {
+    Parser::new(string, ParserOptions::default()).parse_prefix(&mut ValueBuilder)
+}
+
+/// Decodes the escape sequences in `string`, which is taken to be the
+/// contents of a JSON string without its surrounding quotes.
+///
+/// Every escape a document may hold is understood, including `\\uXXXX`
+/// surrogate pairs. This is the inverse of [`escape_string`](crate::escape_string).
+pub fn unescape_string(string: &str) -> core::result::Result<String, ParseError> {
+    Parser::new(string, ParserOptions::default()).unescape_fragment()
+}
+
+/// Iterates over the JSON documents in `string`, which follow one another
+/// separated only by optional whitespace, as some streaming protocols send
+/// them.
+///
+/// Iteration stops cleanly at the end of the input, including after trailing
+/// whitespace. The first malformed document is yielded as an error, with its
+/// position counted from the start of `string`, and ends the iteration.
+pub fn parse_many(string: &str) -> Documents<'_> {
+    Documents {
+        parser: Some(Parser::new(string, ParserOptions::default())),
+    }
+}
+
+/// The iterator returned by [`parse_many`].
+pub struct Documents<'a> {
+    /// `None` once an error has been yielded.
+    parser: Option<Parser<'a>>,
+}
+
+impl Iterator for Documents<'_> {
+    type Item = ParseResult;
+
+    fn next(&mut self) -> Option<ParseResult> {
+        let parser = self.parser.as_mut()?;
+        let result = match parser.consume_whitespace() {
+            Ok(()) if parser.chars.peek().is_none() => return None,
+            Ok(()) => parser
+                .parse_prefix(&mut ValueBuilder)
+                .map(|(value, _)| value),
+            Err(error) => Err(error),
+        };
+
+        if result.is_err() {
+            self.parser = None;
+        }
+
+        Some(result)
+    }
+}
+
+/// Parses newline-delimited JSON, where each line holds one complete
+/// document. Blank lines are skipped.
+///
+/// Errors are positioned within the whole input, so `line` is the line of
+/// the first malformed document.
+pub fn parse_ndjson(string: &str) -> core::result::Result<Vec<Value>, ParseError> {
+    let mut values = Vec::new();
+    let mut line_start = 0;
+
+    for (index, line) in string.split('\n').enumerate() {
+        if !line
+            .bytes()
+            .all(|byte| matches!(byte, b' ' | b'\t' | b'\r'))
+        {
+            let value = parse(line).map_err(|error| ParseError {
+                offset: line_start + error.offset,
+                line: index + 1,
+                line_delimited: true,
+                ..error
+            })?;
+            values.push(value);
+        }
+
+        line_start += line.len() + 1;
+    }
+
+    Ok(values)
+}
+
+/// Parses a complete document, carrying on past errors to report as many as
+/// possible, as an editor or linter would want.
+///
+/// After an error in an array element or object member, the rest of it is
+/// skipped and parsing resumes at the next `,`, `]` or `}`. The value is
+/// returned, minus whatever had to be skipped, if the end of the document
+/// could still be reached. Recovery is best-effort: a single mistake may
+/// produce several errors.
+pub fn parse_lenient_collect(string: &str) -> (Option<Value>, Vec<ParseError>) {
+    let mut parser = Parser::new(string, ParserOptions::default());
+    parser.errors = Some(Vec::new());
+
+    let result = parser.parse(&mut ValueBuilder);
+    let mut errors = parser.errors.take().unwrap_or_default();
+    match result {
+        Ok(value) => (Some(value), errors),
+        Err(error) => {
+            record(&mut errors, error);
+            (None, errors)
+        }
+    }
+}
+
+/// Adds `error` to `errors`, unless it is the last error seen again, as
+/// happens when a failure can only be recovered from further out.
+fn record(errors: &mut Vec<ParseError>, error: ParseError) {
+    if errors.last().map(|last| last.offset) != Some(error.offset) {
+        errors.push(error);
+    }
+}
+
+/// Parses a complete document in a lenient dialect that additionally
+/// accepts `//` and `/* */` comments, trailing commas, `NaN`, `Infinity`
+/// and `-Infinity`, and whitespace such as form feeds.
+pub fn parse_json5(string: &str) -> ParseResult {
+    parse_with_options(string, ParserOptions::json5())
+}
+
+/// Parses a complete JSON document using the given options.
+pub fn parse_with_options(string: &str, options: ParserOptions) -> ParseResult {
+    ParseContext::new(options).parse(string)
+}
+
+/// Parses one document after another with the same options, reusing the
+/// memory that parsing needs from one to the next.
+///
+/// Strings containing escapes are decoded in a scratch buffer, which this
+/// keeps between calls, so a loop over many small documents stops
+/// allocating for it once it has grown to fit. Nesting is tracked by
+/// recursion, so there is no stack to keep.
+#[derive(Debug, Default)]
+pub struct ParseContext {
+    options: ParserOptions,
+    scratch: String,
+}
+
+impl ParseContext {
+    pub fn new(options: ParserOptions) -> Self {
+        ParseContext {
+            options,
+            scratch: String::new(),
+        }
+    }
+
+    /// Parses a complete JSON document, as [`parse_with_options`] does.
+    pub fn parse(&mut self, string: &str) -> ParseResult {
+        let mut parser = Parser::new(string, self.options.clone());
+        parser.scratch = core::mem::take(&mut self.scratch);
+
+        let result = match &self.options.number_sink {
+            Some(sink) => parser.parse(&mut SinkBuilder {
+                sink: &**sink,
+                values: ValueBuilder,
+            }),
+            None => parser.parse(&mut ValueBuilder),
+        };
+
+        self.scratch = parser.scratch;
+        result
+    }
+}
+
+/// Parses a complete JSON document from bytes that may not all be valid
+/// UTF-8, repairing rather than rejecting what it can.
+///
+/// Each invalid UTF-8 sequence, and each `\\u` escape of an unpaired
+/// surrogate, becomes U+FFFD REPLACEMENT CHARACTER. A replaced sequence
+/// outside a string is still an error, and error positions refer to the
+/// repaired text. [`parse_bytes`] rejects all of these instead.
+pub fn parse_lossy(bytes: &[u8]) -> ParseResult {
+    let string = String::from_utf8_lossy(bytes);
+    parse_with_options(
+        &string,
+        ParserOptions {
+            replace_lone_surrogates: true,
+            ..ParserOptions::default()
+        },
+    )
+}
+
+/// Parses a complete JSON document from UTF-8 encoded bytes.
+///
+/// Bytes that are not valid UTF-8 produce an [`ErrorKind::InvalidUtf8`] error
+/// pointing at the first invalid byte.
+pub fn parse_bytes(bytes: &[u8]) -> ParseResult {
+    match core::str::from_utf8(bytes) {
+        Ok(string) => parse(string),
+        Err(error) => {
+            let offset = error.valid_up_to();
+            // The prefix up to `offset` is valid, so this cannot fail.
+            let valid = core::str::from_utf8(&bytes[..offset]).unwrap();
+
+            Err(error_at(valid, ErrorKind::InvalidUtf8, offset))
+        }
+    }
+}
+
+/// Reads `reader` to the end and parses its contents as a complete JSON
+/// document.
+#[cfg(feature = "std")]
+pub fn parse_reader<R: Read>(mut reader: R) -> ParseResult {
+    let mut bytes = Vec::new();
+    if let Err(error) = reader.read_to_end(&mut bytes) {
+        return Err(ParseError {
+            kind: ErrorKind::Io(error.kind()),
+            offset: bytes.len(),
+            line: 1,
+            column: 1,
+            path: None,
+            line_delimited: false,
+        });
+    }
+
+    parse_bytes(&bytes)
+}
+
+/// Reads one message from a stream in which each is a JSON document
+/// preceded by its length in bytes, as a 4-byte big-endian integer.
+///
+/// Returns `None` if the stream ends cleanly before the next message. A
+/// stream that ends partway through a message fails with
+/// [`io::ErrorKind::UnexpectedEof`], and a message that does not parse fails
+/// with [`io::ErrorKind::InvalidData`] wrapping the [`ParseError`].
+#[cfg(feature = "std")]
+pub fn parse_framed<R: Read>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut header = [0; 4];
+    let mut filled = 0;
+    while filled < header.len() {
+        match reader.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    // Read through `take` rather than allocating the whole claimed length up
+    // front, so a corrupt header cannot exhaust memory on its own.
+    let len = u32::from_be_bytes(header) as u64;
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if (bytes.len() as u64) < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    parse_bytes(&bytes)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        parse, parse_bytes, parse_framed, parse_json5, parse_lenient_collect, parse_lossy,
+        parse_many, parse_ndjson, parse_prefix, parse_reader, parse_with_options, validate,
+        ErrorKind, NumberSink, ParseContext, ParseError, ParseResult, ParserOptions, Value,
+    };
+
+    fn error(kind: ErrorKind, offset: usize, line: usize, column: usize) -> ParseResult {
+        Err(ParseError {
+            kind,
+            offset,
+            line,
+            column,
+            path: None,
+            line_delimited: false,
+        })
+    }
+
+    fn unexpected(ch: char, offset: usize, line: usize, column: usize) -> ParseResult {
+        error(ErrorKind::UnexpectedToken(ch), offset, line, column)
+    }
+
+    fn expected(
+        expected: &'static str,
+        found: char,
+        offset: usize,
+        line: usize,
+        column: usize,
+    ) -> ParseResult {
+        error(
+            ErrorKind::Expected { expected, found },
+            offset,
+            line,
+            column,
+        )
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn test_parse() {
+        assert_eq!(parse("null"), Ok(Value::Null));
+        assert_eq!(parse("true"), Ok(Value::Bool(true)));
+        assert_eq!(parse("false"), Ok(Value::Bool(false)));
+        assert_eq!(parse("   null   "), Ok(Value::Null));
+        assert_eq!(parse("\"\""), Ok(Value::String("".to_owned())));
+        assert_eq!(parse("\"\\u0041\""), Ok(Value::String("A".to_owned())));
+        assert_eq!(parse("\"null\""), Ok(Value::String("null".to_owned())));
+        assert_eq!(
+            parse("\"nu\\\"ll\""),
+            Ok(Value::String("nu\"ll".to_owned()))
+        );
+        assert_eq!(
+            parse("\"nu\\\\ll\""),
+            Ok(Value::String("nu\\ll".to_owned()))
+        );
+        assert_eq!(parse("\"nu\\\\ll\"1"), unexpected('1', 8, 1, 9));
+        assert_eq!(parse("\"nu\\\\ll\"  1"), unexpected('1', 10, 1, 11));
+        assert_eq!(
+            parse("\"nu\\\\ll"),
+            error(ErrorKind::UnterminatedString, 0, 1, 1)
+        );
+        assert_eq!(parse("0"), Ok(Value::Integer(0)));
+        assert_eq!(parse("-0"), Ok(Value::Number(-0f64)));
+        assert_eq!(parse("1"), Ok(Value::Integer(1)));
+        assert_eq!(parse("1.0"), Ok(Value::Number(1.0)));
+        assert_eq!(parse("3.14"), Ok(Value::Number(3.14)));
+        assert_eq!(parse("[]"), Ok(Value::Array(Vec::new())));
+        assert_eq!(parse("["), error(ErrorKind::UnexpectedEof, 1, 1, 2));
+        assert_eq!(parse("   [     ]   "), Ok(Value::Array(Vec::new())));
+        assert_eq!(
+            parse("[1]"),
+            Ok(Value::Array(Vec::from([Value::Integer(1)])))
+        );
+        assert_eq!(
+            parse("  [  1  ]  "),
+            Ok(Value::Array(Vec::from([Value::Integer(1)])))
+        );
+        assert_eq!(parse("[1,]"), unexpected(']', 3, 1, 4));
+        assert_eq!(parse("[,]"), expected("value", ',', 1, 1, 2));
+        assert_eq!(
+            parse("[[[]]]"),
+            Ok(Value::Array(Vec::from([Value::Array(Vec::from([
+                Value::Array(Vec::new())
+            ]))])))
+        );
+        assert_eq!(
+            parse("[[[1], 2], 3]"),
+            Ok(Value::Array(Vec::from([
+                Value::Array(Vec::from([
+                    Value::Array(Vec::from([Value::Integer(1)])),
+                    Value::Integer(2),
+                ])),
+                Value::Integer(3)
+            ])))
+        );
+        assert_eq!(parse("{}"), Ok(Value::Object(Vec::new())));
+        assert_eq!(
+            parse("{ \"hello\" : \"world\"  }  "),
+            Ok(Value::Object(Vec::from([(
+                "hello".to_owned(),
+                Value::String("world".to_owned())
+            )])))
+        );
+        assert_eq!(
+            parse("{ \"c\" : \"u\"  ,  \"l\": 8  }  "),
+            Ok(Value::Object(Vec::from([
+                ("c".to_owned(), Value::String("u".to_owned())),
+                ("l".to_owned(), Value::Integer(8))
+            ])))
+        );
+        assert_eq!(
+            parse("{\"c\":true,\"l\":null}"),
+            Ok(Value::Object(Vec::from([
+                ("c".to_owned(), Value::Bool(true)),
+                ("l".to_owned(), Value::Null)
+            ])))
+        );
+        assert_eq!(parse("{\"c\":true,}"), unexpected('}', 10, 1, 11));
+        assert_eq!(parse("{\"c\":tra}"), unexpected('a', 7, 1, 8));
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(
+            parse("9007199254740993"),
+            Ok(Value::Integer(9007199254740993))
+        );
+        assert_eq!(parse("-42"), Ok(Value::Integer(-42)));
+        assert_eq!(parse("-9223372036854775808"), Ok(Value::Integer(i64::MIN)));
+        assert_eq!(
+            parse("9223372036854775808"),
+            Ok(Value::Number(9223372036854775808.0))
+        );
+        assert_eq!(parse("1.0"), Ok(Value::Number(1.0)));
+        assert_eq!(parse("1e3"), Ok(Value::Number(1000.0)));
+        assert_eq!(parse("2E-2"), Ok(Value::Number(0.02)));
+
+        for input in ["-0", "-0.0", "-0e0", "-0.000E+2"] {
+            match parse(input) {
+                Ok(Value::Number(value)) => assert!(value == 0.0 && value.is_sign_negative()),
+                other => panic!("expected a negative zero float, got {:?}", other),
+            }
+        }
+        assert_eq!(parse("0"), Ok(Value::Integer(0)));
+        match parse("0.0") {
+            Ok(Value::Number(value)) => assert!(value == 0.0 && value.is_sign_positive()),
+            other => panic!("expected a positive zero float, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fraction_digits() {
+        assert_eq!(parse("1.5"), Ok(Value::Number(1.5)));
+        assert_eq!(parse("1."), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("1.e5"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("[0.]"), error(ErrorKind::InvalidNumber, 1, 1, 2));
+        assert_eq!(parse("-2.]"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("1e"), error(ErrorKind::UnexpectedEof, 2, 1, 3));
+    }
+
+    #[test]
+    fn test_malformed_numbers() {
+        assert_eq!(parse("+1"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("01"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("00"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("-01"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("[1, +2]"), error(ErrorKind::InvalidNumber, 4, 1, 5));
+        assert_eq!(
+            parse("{\"a\":\n  0123}"),
+            error(ErrorKind::InvalidNumber, 8, 2, 3)
+        );
+        assert_eq!(parse("-"), error(ErrorKind::UnexpectedEof, 1, 1, 2));
+        assert_eq!(parse("0.01"), Ok(Value::Number(0.01)));
+        assert_eq!(parse("-0.5"), Ok(Value::Number(-0.5)));
+        assert_eq!(parse("10"), Ok(Value::Integer(10)));
+        assert_eq!(parse("-10"), Ok(Value::Integer(-10)));
+    }
+
+    #[test]
+    fn test_number_array() {
+        let literals: Vec<String> = (0..20_000i64)
+            .map(|i| match i % 5 {
+                0 => (i * 7919).to_string(),
+                1 => (-i * 104_729).to_string(),
+                2 => format!("{}.{}", i, i % 97),
+                3 => format!("-{}e{}", i, i % 40 - 20),
+                _ => format!("{}.5E+{}", i % 13, i % 300),
+            })
+            .collect();
+        let input = format!("[{}]", literals.join(","));
+
+        let start = std::time::Instant::now();
+        let value = parse(&input).unwrap();
+        let elapsed = start.elapsed();
+
+        let expected: Vec<Value> = literals
+            .iter()
+            .map(|literal| match literal.parse::<i64>() {
+                Ok(integer) => Value::Integer(integer),
+                Err(_) => Value::Number(literal.parse().unwrap()),
+            })
+            .collect();
+        assert_eq!(value, Value::Array(expected), "parsed in {:?}", elapsed);
+    }
+
+    #[test]
+    fn test_exponents() {
+        for (input, expected) in [
+            ("1e5", 1e5),
+            ("1E5", 1e5),
+            ("1e+5", 1e5),
+            ("1E+5", 1e5),
+            ("1e-5", 1e-5),
+            ("1.5e10", 1.5e10),
+            ("-2.5E-3", -2.5e-3),
+            ("0e5", 0.0),
+            ("0.0e0", 0.0),
+            ("1e007", 1e7),
+        ] {
+            let value = parse(input).unwrap();
+            assert_eq!(value, Value::Number(expected), "{}", input);
+            assert_eq!(parse(&value.serialize()), Ok(value), "{}", input);
+        }
+
+        let raw = ParserOptions {
+            raw_numbers: true,
+            ..ParserOptions::default()
+        };
+        let input = "[1e5,1E5,1e+5,1e-5,1.5e10]";
+        assert_eq!(parse_with_options(input, raw).unwrap().serialize(), input);
+
+        assert_eq!(parse("1e"), error(ErrorKind::UnexpectedEof, 2, 1, 3));
+        assert_eq!(parse("1e+"), error(ErrorKind::UnexpectedEof, 3, 1, 4));
+        assert_eq!(parse("1E-x"), unexpected('x', 3, 1, 4));
+        assert_eq!(parse("1e+-5"), unexpected('-', 3, 1, 4));
+        assert_eq!(parse("1e5.0"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("1.e5"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+    }
+
+    #[test]
+    fn test_zero_exponents() {
+        for (input, expected) in [
+            ("0e0", 0.0),
+            ("0E+0", 0.0),
+            ("0.0e0", 0.0),
+            ("0e-7", 0.0),
+            ("-0e5", -0.0),
+            ("-0E+0", -0.0),
+        ] {
+            for input in [input.to_owned(), format!("[{}]", input)] {
+                let value = parse(&input).unwrap();
+                let number = value.as_f64().or_else(|| value[0].as_f64());
+                assert_eq!(
+                    number.map(f64::to_bits),
+                    Some(f64::to_bits(expected)),
+                    "{}",
+                    input
+                );
+            }
+        }
+
+        assert_eq!(parse("0e"), error(ErrorKind::UnexpectedEof, 2, 1, 3));
+        assert_eq!(parse("00e0"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+    }
+
+    #[test]
+    fn test_number_followed_by_letter() {
+        assert_eq!(parse("1abc"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("[1abc]"), error(ErrorKind::InvalidNumber, 1, 1, 2));
+        assert_eq!(parse("1.2.3"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(
+            parse("{\"a\": -12x}"),
+            error(ErrorKind::InvalidNumber, 6, 1, 7)
+        );
+        assert_eq!(parse("[1e5_]"), error(ErrorKind::InvalidNumber, 1, 1, 2));
+        assert_eq!(parse("[1 a]"), expected("',' or ']'", 'a', 3, 1, 4));
+        assert_eq!(parse("[1,2]"), parse("[1 , 2]"));
+    }
+
+    #[test]
+    fn test_number_overflow() {
+        assert_eq!(parse("1e400"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(parse("-1e400"), error(ErrorKind::InvalidNumber, 0, 1, 1));
+        assert_eq!(
+            parse(&format!("[1{}]", "0".repeat(400))),
+            error(ErrorKind::InvalidNumber, 1, 1, 2)
+        );
+        assert_eq!(parse("1e308"), Ok(Value::Number(1e308)));
+        assert_eq!(parse("1e-400"), Ok(Value::Number(0.0)));
+    }
+
+    #[test]
+    fn test_error_location() {
+        assert_eq!(parse("[\n  1,\n  2,\n  x\n]"), unexpected('x', 14, 4, 3));
+        assert_eq!(
+            parse("{\r\n\t\"a\": 1\r\n}\r\n}"),
+            unexpected('}', 15, 4, 1)
+        );
+        assert_eq!(
+            parse("[\"multi\\nline\",\n \"caf\u{e9}\", \"\u{e9}\u{e9}\" ?]"),
+            expected("',' or ']'", '?', 33, 2, 15)
+        );
+        assert_eq!(parse("\"a\nb\"\n\n   ,"), unexpected(',', 10, 4, 4));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        assert_eq!(
+            parse("\"\\x\""),
+            error(ErrorKind::InvalidEscape('x'), 2, 1, 3)
+        );
+        assert_eq!(
+            parse("\"\\u00g0\""),
+            error(ErrorKind::InvalidUnicode, 5, 1, 6)
+        );
+        assert_eq!(
+            parse("\"\\ud800\""),
+            error(ErrorKind::InvalidUnicode, 2, 1, 3)
+        );
+        assert_eq!(
+            parse("\"\\ud800\\u0041\""),
+            error(ErrorKind::InvalidUnicode, 2, 1, 3)
+        );
+        assert_eq!(
+            parse("\"\\udc00\""),
+            error(ErrorKind::InvalidUnicode, 2, 1, 3)
+        );
+        assert_eq!(
+            parse("\"\\u00"),
+            error(ErrorKind::UnterminatedString, 0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            parse("[\n  1,\n  2,\n  x\n]").unwrap_err().to_string(),
+            "Unexpected token 'x' at line 4, column 3"
+        );
+        assert_eq!(
+            parse("[").unwrap_err().to_string(),
+            "Unexpected end of input"
+        );
+        assert_eq!(
+            parse("\"\\x\"").unwrap_err().to_string(),
+            "Invalid escape '\\x' at line 1, column 3"
+        );
+        assert_eq!(
+            parse("\"\\ud800\"").unwrap_err().to_string(),
+            "Invalid unicode escape at line 1, column 3"
+        );
+        assert_eq!(
+            parse("{\n  \"a\": 1,\n  \"b\": \"c\n}")
+                .unwrap_err()
+                .to_string(),
+            "Unterminated string starting at line 3, column 8"
+        );
+    }
+
+    #[test]
+    fn test_expected_punctuation() {
+        let message = |input: &str| parse(input).unwrap_err().to_string();
+
+        assert_eq!(
+            message("{\"a\" 1}"),
+            "Expected ':' but found '1' at line 1, column 6"
+        );
+        assert_eq!(
+            message("[1 2]"),
+            "Expected ',' or ']' but found '2' at line 1, column 4"
+        );
+        assert_eq!(
+            message("{\"a\": 1 \"b\": 2}"),
+            "Expected ',' or '}' but found '\"' at line 1, column 9"
+        );
+        assert_eq!(
+            message("[1}"),
+            "Expected ',' or ']' but found '}' at line 1, column 3"
+        );
+        assert_eq!(
+            message("{\"a\": 1]"),
+            "Expected ',' or '}' but found ']' at line 1, column 8"
+        );
+        assert_eq!(message("{\"a\""), "Unexpected end of input");
+    }
+
+    #[test]
+    fn test_unterminated_string() {
+        assert_eq!(
+            parse("{\"a\": [1, \"bc\\\"d]}"),
+            error(ErrorKind::UnterminatedString, 10, 1, 11)
+        );
+        assert_eq!(
+            parse("{\"a\": 1, \"b"),
+            error(ErrorKind::UnterminatedString, 9, 1, 10)
+        );
+        assert_eq!(parse("\""), error(ErrorKind::UnterminatedString, 0, 1, 1));
+    }
+
+    #[test]
+    fn test_truncated_escapes() {
+        for input in [
+            "\"\\",
+            "\"\\\\",
+            "\"\\\"",
+            "\"\\u",
+            "\"\\u12",
+            "\"a\\u00e9\\",
+        ] {
+            assert_eq!(
+                parse(input),
+                error(ErrorKind::UnterminatedString, 0, 1, 1),
+                "{:?}",
+                input
+            );
+            assert_eq!(validate(input).err(), parse(input).err(), "{:?}", input);
+        }
+        assert_eq!(
+            parse("[\"a\",\n \"\\u12"),
+            error(ErrorKind::UnterminatedString, 7, 2, 2)
+        );
+        assert_eq!(
+            parse("\"\\u12\""),
+            error(ErrorKind::InvalidUnicode, 5, 1, 6)
+        );
+        assert_eq!(
+            parse("\"\\u12\"").unwrap_err().to_string(),
+            "Invalid unicode escape at line 1, column 6"
+        );
+    }
+
+    #[test]
+    fn test_object_keys() {
+        let value = parse(
+            "{\"\": 0, \"plain\": 1, \"esc\\\"aped\\n\": 2, \"\\u00e9t\u{e9}\": 3, \"\\\\\": 4}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            value.entries().map(|(key, _)| key).collect::<Vec<_>>(),
+            ["", "plain", "esc\"aped\n", "\u{e9}t\u{e9}", "\\"]
+        );
+        assert_eq!(
+            parse("{\"a\\x\": 1}"),
+            error(ErrorKind::InvalidEscape('x'), 4, 1, 5)
+        );
+        assert_eq!(parse("{\"a\" 1}"), expected("':'", '1', 5, 1, 6));
+        assert_eq!(parse("{a: 1}"), unexpected('a', 1, 1, 2));
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let strict = ParserOptions {
+            reject_duplicate_keys: true,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(
+            parse("{\"a\":1,\"a\":2}"),
+            Ok(Value::Object(Vec::from([
+                ("a".to_owned(), Value::Integer(1)),
+                ("a".to_owned(), Value::Integer(2))
+            ])))
+        );
+        assert_eq!(
+            parse_with_options("{\"a\":1,\n \"b\":2,\n \"a\":3}", strict.clone()),
+            error(ErrorKind::DuplicateKey("a".to_owned()), 17, 3, 2)
+        );
+        assert_eq!(
+            parse_with_options("{\"a\":{\"a\":1},\"b\":[{\"a\":1}]}", strict.clone()),
+            parse("{\"a\":{\"a\":1},\"b\":[{\"a\":1}]}")
+        );
+        assert_eq!(
+            parse_with_options("[{\"a\":1,\"a\":2}]", strict)
+                .unwrap_err()
+                .to_string(),
+            "Duplicate key 'a' at line 1, column 9"
+        );
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+
+        assert!(parse(&nested(128)).is_ok());
+        assert_eq!(
+            parse(&nested(129)),
+            error(ErrorKind::DepthLimitExceeded, 128, 1, 129)
+        );
+        assert_eq!(
+            parse(&"[".repeat(10_000)),
+            error(ErrorKind::DepthLimitExceeded, 128, 1, 129)
+        );
+        assert_eq!(
+            parse(&"{\"a\":".repeat(10_000)).map_err(|error| error.kind),
+            Err(ErrorKind::DepthLimitExceeded)
+        );
+        assert_eq!(
+            parse_with_options(
+                &nested(3),
+                ParserOptions {
+                    max_depth: 2,
+                    ..ParserOptions::default()
+                }
+            )
+            .unwrap_err()
+            .to_string(),
+            "Nesting depth limit exceeded at line 1, column 3"
+        );
+    }
+
+    #[test]
+    fn test_length_limits() {
+        let limited = ParserOptions {
+            max_input_length: 16,
+            max_number_length: 8,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(
+            parse_with_options("[1, 2, 3, 4, 5, 6, 7]", limited.clone()),
+            error(ErrorKind::InputTooLarge, 0, 1, 1)
+        );
+        assert_eq!(
+            parse_with_options("[1, 1234567890]", limited.clone()),
+            error(ErrorKind::NumberTooLong, 4, 1, 5)
+        );
+        assert_eq!(
+            parse_with_options("[-1.2e+34, 5]", limited.clone()),
+            Ok(Value::Array(Vec::from([
+                Value::Number(-1.2e34),
+                Value::Integer(5)
+            ])))
+        );
+        assert_eq!(
+            parse_with_options("[1, 2, 3, 4, 5, 6, 7]", limited)
+                .unwrap_err()
+                .to_string(),
+            "Input exceeds the maximum length"
+        );
+        assert_eq!(
+            parse(&format!("0.{}", "1".repeat(2000))),
+            error(ErrorKind::NumberTooLong, 0, 1, 1)
+        );
+        assert!(parse(&format!("0.{}", "1".repeat(1000))).is_ok());
+    }
+
+    #[test]
+    fn test_max_values() {
+        let limited = ParserOptions {
+            max_values: 4,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(
+            parse_with_options("[1, 2, 3]", limited.clone()),
+            Ok(Value::Array(Vec::from([
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(3)
+            ])))
+        );
+        assert_eq!(
+            parse_with_options("[1, 2, 3, 4]", limited.clone()),
+            error(ErrorKind::TooManyValues, 10, 1, 11)
+        );
+        assert_eq!(
+            parse_with_options(
+                "{\"a\": [], \"b\": {\"c\": null, \"d\": 1}}",
+                limited.clone()
+            ),
+            error(ErrorKind::TooManyValues, 32, 1, 33)
+        );
+        assert_eq!(
+            parse_with_options(&format!("[{}0]", "0,".repeat(100_000)), limited)
+                .unwrap_err()
+                .to_string(),
+            "Too many values at line 1, column 8"
+        );
+    }
+
+    #[test]
+    fn test_max_string_length() {
+        let limited = ParserOptions {
+            max_string_length: 3,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(
+            parse_with_options(
+                "[\"abc\", \"\\u00e9\\n\\t\", \"\u{1f600}ab\"]",
+                limited.clone()
+            ),
+            Ok(Value::Array(Vec::from([
+                Value::from("abc"),
+                Value::from("\u{e9}\n\t"),
+                Value::from("\u{1f600}ab")
+            ])))
+        );
+        assert_eq!(
+            parse_with_options("[1, \"abcd\"]", limited.clone()),
+            error(ErrorKind::StringTooLong, 4, 1, 5)
+        );
+        assert_eq!(
+            parse_with_options("\"a\\nbc\"", limited.clone()),
+            error(ErrorKind::StringTooLong, 0, 1, 1)
+        );
+        assert_eq!(
+            parse_with_options("{\"long key\": 1}", limited.clone())
+                .unwrap_err()
+                .to_string(),
+            "String too long at line 1, column 2"
+        );
+        assert_eq!(
+            parse_with_options(&format!("[\"{}\"]", "x".repeat(10_000)), limited),
+            error(ErrorKind::StringTooLong, 1, 1, 2)
+        );
+    }
+
+    #[test]
+    fn test_raw_numbers() {
+        let raw = ParserOptions {
+            raw_numbers: true,
+            ..ParserOptions::default()
+        };
+        let input = "[9999999999999999, 1.000000000000000001, -0, 0, 1E+400, 2e-3]";
+
+        assert_eq!(
+            parse_with_options(input, raw),
+            Ok(Value::Array(
+                [
+                    "9999999999999999",
+                    "1.000000000000000001",
+                    "-0",
+                    "0",
+                    "1E+400",
+                    "2e-3"
+                ]
+                .into_iter()
+                .map(|raw| Value::RawNumber(raw.to_owned()))
+                .collect()
+            ))
+        );
+        assert_eq!(
+            parse("[9999999999999999.0, 1.000000000000000001]"),
+            Ok(Value::Array(Vec::from([
+                Value::Number(1e16),
+                Value::Number(1.0)
+            ])))
+        );
+        assert_eq!(
+            parse_with_options(
+                "[01]",
+                ParserOptions {
+                    raw_numbers: true,
+                    ..ParserOptions::default()
+                }
+            ),
+            error(ErrorKind::InvalidNumber, 1, 1, 2)
+        );
+    }
+
+    #[test]
+    fn test_number_sink() {
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<String>>);
+
+        impl NumberSink for Recorder {
+            fn number(&self, literal: &str) -> Value {
+                self.0.lock().unwrap().push(literal.to_owned());
+                Value::String(literal.to_owned())
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let options = ParserOptions {
+            number_sink: Some(recorder.clone()),
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(
+            parse_with_options(
+                "[0.1, {\"a\": -2E+400}, \"3\", 12345678901234567890]",
+                options.clone()
+            ),
+            parse("[\"0.1\", {\"a\": \"-2E+400\"}, \"3\", \"12345678901234567890\"]")
+        );
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            ["0.1", "-2E+400", "12345678901234567890"]
+        );
+        assert_eq!(
+            parse_with_options("[1.]", options),
+            error(ErrorKind::InvalidNumber, 1, 1, 2)
+        );
+    }
+
+    #[test]
+    fn test_whitespace_placement() {
+        let tokens = [
+            "{", "\"a\"", ":", "[", "1", ",", "-2.5e3", ",", "true", ",", "null", ",", "\"s\"",
+            ",", "{", "}", ",", "[", "]", "]", ",", "\"b\"", ":", "{", "\"c\"", ":", "0", "}", "}",
+        ];
+        let compact = parse(&tokens.concat()).unwrap();
+
+        for whitespace in [" ", "\t", "\n", "\r\n", " \t\r\n "] {
+            // Around every token at once, including before the first and
+            // after the last.
+            let input = format!("{0}{1}{0}", whitespace, tokens.join(whitespace));
+            assert_eq!(parse(&input).as_ref(), Ok(&compact), "{:?}", input);
+
+            // At each boundary on its own.
+            for i in 0..=tokens.len() {
+                let input = format!(
+                    "{}{}{}",
+                    tokens[..i].concat(),
+                    whitespace,
+                    tokens[i..].concat()
+                );
+                assert_eq!(parse(&input).as_ref(), Ok(&compact), "{:?}", input);
+            }
+        }
+
+        for input in [
+            "1 ",
+            "-0.5\n",
+            "\"s\"\t",
+            "true\r\n",
+            "[1 ,2 ]",
+            "{\"a\" :1 }",
+        ] {
+            assert!(parse(input).is_ok(), "{:?}", input);
+        }
+
+        // Whitespace cannot split a token.
+        assert_eq!(parse("tr ue"), unexpected(' ', 2, 1, 3));
+        assert_eq!(parse("- 1"), unexpected(' ', 1, 1, 2));
+        assert_eq!(parse("1 .5"), unexpected('.', 2, 1, 3));
+        assert_eq!(parse("1e 5"), unexpected(' ', 2, 1, 3));
+        assert_eq!(parse("[1 2]"), expected("',' or ']'", '2', 3, 1, 4));
+    }
+
+    #[test]
+    fn test_whitespace() {
+        assert_eq!(parse(" \t\r\n1 \t\r\n"), Ok(Value::Integer(1)));
+
+        for ch in [
+            '\u{000B}', '\u{000C}', '\u{00A0}', '\u{2028}', '\u{2029}', '\u{FEFF}',
+        ] {
+            assert_eq!(
+                parse(&format!("{}1", ch)),
+                unexpected(ch, 0, 1, 1),
+                "{:?}",
+                ch
+            );
+            assert_eq!(
+                parse(&format!("[1,{}2]", ch)),
+                unexpected(ch, 3, 1, 4),
+                "{:?}",
+                ch
+            );
+            assert_eq!(
+                parse_json5(&format!("{0}[1,{0}2{0}]{0}", ch)),
+                parse("[1,2]"),
+                "{:?}",
+                ch
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_finite() {
+        let value = parse_json5("[NaN, Infinity, -Infinity, -1]").unwrap();
+        match value.as_array() {
+            Some(
+                [Value::Number(nan), Value::Number(f64::INFINITY), Value::Number(f64::NEG_INFINITY), Value::Integer(-1)],
+            ) => assert!(nan.is_nan()),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(value.serialize(), "[null,null,null,-1]");
+
+        assert_eq!(parse("NaN"), unexpected('N', 0, 1, 1));
+        assert_eq!(parse("[Infinity]"), unexpected('I', 1, 1, 2));
+        assert_eq!(parse("-Infinity"), unexpected('I', 1, 1, 2));
+        assert_eq!(
+            parse_json5("Inf"),
+            error(ErrorKind::TruncatedLiteral("Infinity"), 3, 1, 4)
+        );
+        assert_eq!(parse_json5("nan"), unexpected('a', 1, 1, 2));
+
+        let both = ParserOptions {
+            case_insensitive_literals: true,
+            ..ParserOptions::json5()
+        };
+        assert_eq!(parse_with_options("NULL", both.clone()), Ok(Value::Null));
+        assert!(parse_with_options("NaN", both)
+            .unwrap()
+            .as_f64()
+            .unwrap()
+            .is_nan());
+
+        struct Stringify;
+
+        impl NumberSink for Stringify {
+            fn number(&self, literal: &str) -> Value {
+                Value::String(literal.to_owned())
+            }
+        }
+
+        for options in [
+            ParserOptions {
+                raw_numbers: true,
+                ..ParserOptions::json5()
+            },
+            ParserOptions {
+                number_sink: Some(Arc::new(Stringify)),
+                ..ParserOptions::json5()
+            },
+        ] {
+            let value = parse_with_options("[Infinity, -Infinity, NaN, -1]", options).unwrap();
+            assert_eq!(value[0], Value::Number(f64::INFINITY));
+            assert_eq!(value[1], Value::Number(f64::NEG_INFINITY));
+            assert!(value[2].as_f64().unwrap().is_nan());
+            // Only the real number literal goes through the raw or sink path.
+            assert_ne!(value[3], Value::Integer(-1));
+        }
+    }
+
+    #[test]
+    fn test_case_insensitive_literals() {
+        let lenient = ParserOptions {
+            case_insensitive_literals: true,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(parse("True"), unexpected('T', 0, 1, 1));
+        assert_eq!(parse("[nUll]"), unexpected('U', 2, 1, 3));
+        assert_eq!(parse_json5("FALSE"), unexpected('F', 0, 1, 1));
+        assert_eq!(
+            parse_with_options("[True, FALSE, Null, nULL, tRuE]", lenient.clone()),
+            parse("[true, false, null, null, true]")
+        );
+        assert_eq!(
+            parse_with_options("Tru", lenient.clone()),
+            error(ErrorKind::TruncatedLiteral("true"), 3, 1, 4)
+        );
+        assert_eq!(
+            parse_with_options("Nil", lenient.clone()),
+            unexpected('i', 1, 1, 2)
+        );
+        assert_eq!(parse_with_options("Yes", lenient), unexpected('Y', 0, 1, 1));
+    }
+
+    #[test]
+    fn test_allow_trailing() {
+        let trailing = ParserOptions {
+            allow_trailing: true,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(parse("null123"), unexpected('1', 4, 1, 5));
+        assert_eq!(
+            parse_with_options("null123", ParserOptions::default()),
+            unexpected('1', 4, 1, 5)
+        );
+        assert_eq!(
+            parse_with_options("null123", trailing.clone()),
+            Ok(Value::Null)
+        );
+        assert_eq!(
+            parse_with_options("[1] {\"a\": x", trailing.clone()),
+            parse("[1]")
+        );
+        assert_eq!(
+            parse_with_options("[1, x] 2", trailing),
+            unexpected('x', 4, 1, 5)
+        );
+    }
+
+    #[test]
+    fn test_require_container() {
+        let strict = ParserOptions {
+            require_container: true,
+            ..ParserOptions::default()
+        };
+
+        assert_eq!(parse(" 42"), Ok(Value::Integer(42)));
+        assert_eq!(
+            parse_with_options(" 42", strict.clone()),
+            error(ErrorKind::ScalarRoot, 1, 1, 2)
+        );
+        assert_eq!(
+            parse_with_options("\"foo\"", strict.clone()),
+            error(ErrorKind::ScalarRoot, 0, 1, 1)
+        );
+        assert_eq!(parse_with_options(" [42] ", strict.clone()), parse("[42]"));
+        assert_eq!(parse_with_options("{}", strict.clone()), parse("{}"));
+        assert_eq!(
+            parse_with_options("  ", strict),
+            error(ErrorKind::EmptyInput, 2, 1, 3)
+        );
+    }
+
+    #[test]
+    fn test_error_paths() {
+        let paths = ParserOptions {
+            error_paths: true,
+            ..ParserOptions::default()
+        };
+        let path = |input: &str| parse_with_options(input, paths.clone()).unwrap_err().path;
+
+        let input =
+            "{\"users\": [{\"name\": \"a\"}, {\"name\": \"b\"}, {\"id\": 3, \"name\": tru}]}";
+        assert_eq!(path(input).as_deref(), Some("$.users[2].name"));
+        assert_eq!(
+            parse_with_options(input, paths.clone())
+                .unwrap_err()
+                .to_string(),
+            "Unexpected token '}' at line 1, column 63 (at $.users[2].name)"
+        );
+        assert_eq!(parse(input).unwrap_err().path, None);
+
+        assert_eq!(
+            path("[1, {\"a b\": [x]}]").as_deref(),
+            Some("$[1][\"a b\"][0]")
+        );
+        assert_eq!(path("{\"a\": [1 2]}").as_deref(), Some("$.a"));
+        assert_eq!(path("{\"a\": {\"b\": ").as_deref(), Some("$.a.b"));
+        assert_eq!(path("nul").as_deref(), Some("$"));
+    }
+
+    #[test]
+    fn test_parse_context() {
+        let documents: Vec<String> = (0..10_000)
+            .map(|i| {
+                format!(
+                    "{{\"id\": {}, \"name\": \"user\\t{}\", \"tags\": [\"\\u00e9\"]}}",
+                    i, i
+                )
+            })
+            .collect();
+        let mut context = ParseContext::default();
+
+        for document in &documents {
+            assert_eq!(context.parse(document), parse(document));
+        }
+        assert!(context.scratch.capacity() >= "user\t9999".len());
+
+        assert_eq!(
+            context.parse("[\"\\x\"]"),
+            error(ErrorKind::InvalidEscape('x'), 3, 1, 4)
+        );
+        assert_eq!(context.parse("\"a\\nb\""), Ok(Value::from("a\nb")));
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(
+            parse_bytes(b"{\"a\": [1, \"caf\xc3\xa9\"]}"),
+            parse("{\"a\": [1, \"caf\u{e9}\"]}")
+        );
+        assert_eq!(
+            parse_bytes(b"[1, 2"),
+            error(ErrorKind::UnexpectedEof, 5, 1, 6)
+        );
+        assert_eq!(
+            parse_bytes(b"[\"\xc3\xa9\",\n \"\xff\"]"),
+            error(ErrorKind::InvalidUtf8, 9, 2, 3)
+        );
+        assert_eq!(
+            parse_bytes(b"\"\xc3\"").unwrap_err().to_string(),
+            "Invalid UTF-8 at line 1, column 2"
+        );
+    }
+
+    #[test]
+    fn test_parse_lossy() {
+        assert_eq!(
+            parse_lossy(b"{\"caf\xc3\xa9\": [\"a\xffb\", \"\xc3\"]}"),
+            parse("{\"caf\u{e9}\": [\"a\u{fffd}b\", \"\u{fffd}\"]}")
+        );
+        assert_eq!(
+            parse_bytes(b"[\"a\xffb\"]"),
+            error(ErrorKind::InvalidUtf8, 3, 1, 4)
+        );
+
+        for (input, expected) in [
+            ("\"\\uD800\"", "\u{fffd}"),
+            ("\"\\udc00x\"", "\u{fffd}x"),
+            ("\"\\uD800\\n\"", "\u{fffd}\n"),
+            ("\"\\uD800\\uD800\\uDC00\"", "\u{fffd}\u{10000}"),
+            ("\"\\ud83d\\ude00\\ude00\"", "\u{1f600}\u{fffd}"),
+        ] {
+            assert_eq!(
+                parse_lossy(input.as_bytes()),
+                Ok(Value::from(expected)),
+                "{}",
+                input
+            );
+            assert_eq!(
+                parse_bytes(input.as_bytes()).map_err(|error| error.kind),
+                Err(ErrorKind::InvalidUnicode),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(parse_lossy(b"[1, \xff]"), unexpected('\u{fffd}', 4, 1, 5));
+        assert_eq!(
+            parse_lossy(b"\"\\uD800"),
+            error(ErrorKind::UnterminatedString, 0, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        struct Failing;
+
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::PermissionDenied.into())
+            }
+        }
+
+        assert_eq!(
+            parse_reader(&b"  {\"a\": null}  "[..]),
+            parse("{\"a\": null}")
+        );
+        assert_eq!(
+            parse_reader(&b"\"\xff\""[..]),
+            error(ErrorKind::InvalidUtf8, 1, 1, 2)
+        );
+        assert_eq!(
+            parse_reader(Failing).map_err(|error| error.kind),
+            Err(ErrorKind::Io(std::io::ErrorKind::PermissionDenied))
+        );
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = "// leading\n{\n  \"a\": 1, // trailing\n  /* before key */ \"b\" /* before colon */ : [\n    2, /* between\n elements */ 3 // done\n  ]\n} /* end */";
+
+        assert_eq!(parse_json5(input), parse("{\"a\": 1, \"b\": [2, 3]}"));
+        assert_eq!(parse_json5("/**/1/***/"), Ok(Value::Integer(1)));
+        assert_eq!(
+            parse_json5("[1 // no newline"),
+            error(ErrorKind::UnexpectedEof, 16, 1, 17)
+        );
+        assert_eq!(
+            parse_json5("\"// not a comment\""),
+            Ok(Value::String("// not a comment".to_owned()))
+        );
+        assert_eq!(parse(input), unexpected('/', 0, 1, 1));
+        assert_eq!(parse("[1 /* c */]"), expected("',' or ']'", '/', 3, 1, 4));
+    }
+
+    #[test]
+    fn test_comment_errors() {
+        assert_eq!(
+            parse_json5("[1, /* 2, */ 3 /* 4"),
+            error(ErrorKind::UnterminatedComment, 15, 1, 16)
+        );
+        assert_eq!(
+            parse_json5("1 /* never closed *"),
+            error(ErrorKind::UnterminatedComment, 2, 1, 3)
+        );
+        assert_eq!(parse_json5("[1 / 2]"), unexpected(' ', 4, 1, 5));
+        assert_eq!(
+            parse_json5("{\n/* open").unwrap_err().to_string(),
+            "Unterminated comment at line 2, column 1"
+        );
+    }
+
+    #[test]
+    fn test_stray_commas() {
+        assert_eq!(parse("[,1]"), expected("value", ',', 1, 1, 2));
+        assert_eq!(parse("[1,,2]"), expected("value", ',', 3, 1, 4));
+        assert_eq!(parse("{,}"), expected("key", ',', 1, 1, 2));
+        assert_eq!(
+            parse("{\"a\": 1,, \"b\": 2}"),
+            expected("key", ',', 8, 1, 9)
+        );
+        assert_eq!(parse("{\"a\":,}"), expected("value", ',', 5, 1, 6));
+        assert_eq!(
+            parse("[1,,2]").unwrap_err().to_string(),
+            "Expected value but found ',' at line 1, column 4"
+        );
+    }
+
+    #[test]
+    fn test_trailing_commas() {
+        assert_eq!(parse_json5("[1,2,]"), parse("[1,2]"));
+        assert_eq!(parse_json5("{\"a\":1,}"), parse("{\"a\":1}"));
+        assert_eq!(
+            parse_json5("{ \"a\": [ 1 , ] , /* done */ }"),
+            parse("{\"a\":[1]}")
+        );
+        assert_eq!(parse_json5("[,]"), expected("value", ',', 1, 1, 2));
+        assert_eq!(parse_json5("[1,,]"), expected("value", ',', 3, 1, 4));
+        assert_eq!(parse_json5("{,}"), expected("key", ',', 1, 1, 2));
+
+        assert_eq!(parse("[1,]"), unexpected(']', 3, 1, 4));
+        assert_eq!(parse("{\"c\":true,}"), unexpected('}', 10, 1, 11));
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        let input = "null true";
+
+        let (first, offset) = parse_prefix(input).unwrap();
+        assert_eq!((first, offset), (Value::Null, 5));
+
+        let (second, rest) = parse_prefix(&input[offset..]).unwrap();
+        assert_eq!((second, offset + rest), (Value::Bool(true), input.len()));
+
+        assert_eq!(
+            parse_prefix("  {\"a\":[1]}\n\n{}"),
+            Ok((parse("{\"a\":[1]}").unwrap(), 13))
+        );
+        assert_eq!(parse_prefix("12]"), Ok((Value::Integer(12), 2)));
+        assert_eq!(
+            parse_prefix("[1,"),
+            error(ErrorKind::UnexpectedEof, 3, 1, 4).map(|v| (v, 0))
+        );
+        assert_eq!(
+            parse_prefix(""),
+            error(ErrorKind::EmptyInput, 0, 1, 1).map(|v| (v, 0))
+        );
+    }
+
+    #[test]
+    fn test_parse_framed() {
+        let mut stream = Vec::new();
+        for message in ["{\"id\": 1}", "[true, null]"] {
+            stream.extend((message.len() as u32).to_be_bytes());
+            stream.extend(message.as_bytes());
+        }
+        let mut reader = &stream[..];
+
+        assert_eq!(
+            parse_framed(&mut reader).unwrap(),
+            parse("{\"id\": 1}").ok()
+        );
+        assert_eq!(
+            parse_framed(&mut reader).unwrap(),
+            parse("[true, null]").ok()
+        );
+        assert_eq!(parse_framed(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_framed_errors() {
+        let kind = |bytes: &[u8]| parse_framed(&mut &bytes[..]).unwrap_err().kind();
+
+        assert_eq!(kind(&[0, 0]), io::ErrorKind::UnexpectedEof);
+        assert_eq!(kind(b"\0\0\0\x05[1, "), io::ErrorKind::UnexpectedEof);
+        assert_eq!(kind(b"\xff\xff\xff\xff[]"), io::ErrorKind::UnexpectedEof);
+        assert_eq!(kind(b"\0\0\0\x03[1,"), io::ErrorKind::InvalidData);
+
+        let error = parse_framed(&mut &b"\0\0\0\x02[x"[..]).unwrap_err();
+        assert_eq!(
+            error
+                .into_inner()
+                .unwrap()
+                .downcast::<ParseError>()
+                .unwrap()
+                .kind,
+            ErrorKind::UnexpectedToken('x')
+        );
+    }
+
+    #[test]
+    fn test_parse_many() {
+        assert_eq!(
+            parse_many("1 2 3").collect::<Vec<_>>(),
+            [
+                Ok(Value::Integer(1)),
+                Ok(Value::Integer(2)),
+                Ok(Value::Integer(3))
+            ]
+        );
+        assert_eq!(
+            parse_many("{} []").collect::<Vec<_>>(),
+            [parse("{}"), parse("[]")]
+        );
+        assert_eq!(
+            parse_many("\n{\"a\": [1]}\t\"b\"\r\n ").collect::<Vec<_>>(),
+            [parse("{\"a\": [1]}"), parse("\"b\"")]
+        );
+        assert_eq!(parse_many("").count(), 0);
+        assert_eq!(parse_many(" \n ").count(), 0);
+    }
+
+    #[test]
+    fn test_parse_many_errors() {
+        assert_eq!(
+            parse_many("[1]\n[2,\n[3]").collect::<Vec<_>>(),
+            [parse("[1]"), error(ErrorKind::UnexpectedEof, 11, 3, 4)]
+        );
+        assert_eq!(
+            parse_many("true x true").collect::<Vec<_>>(),
+            [Ok(Value::Bool(true)), unexpected('x', 5, 1, 6)]
+        );
+    }
+
+    #[test]
+    fn test_parse_ndjson() {
+        assert_eq!(
+            parse_ndjson("{\"a\": 1}\n\n[true, null]\r\n\"end\"\n"),
+            Ok(Vec::from([
+                parse("{\"a\": 1}").unwrap(),
+                parse("[true, null]").unwrap(),
+                Value::String("end".to_owned()),
+            ]))
+        );
+        assert_eq!(parse_ndjson(""), Ok(Vec::new()));
+        assert_eq!(
+            parse_ndjson("1\n  \n[1, x]\n3"),
+            Err(ParseError {
+                line_delimited: true,
+                ..unexpected('x', 9, 3, 5).unwrap_err()
+            })
+        );
+        assert_eq!(
+            parse_ndjson("1\n{\"a\": [1, 2]\n}").map_err(|error| (error.kind, error.line)),
+            Err((ErrorKind::UnexpectedEof, 2))
+        );
+
+        // Even errors that usually concern the whole input say which line.
+        let message = |input: &str| parse_ndjson(input).unwrap_err().to_string();
+        assert_eq!(
+            message("1\n{\"a\": [1, 2]\n}"),
+            "Unexpected end of input at line 2, column 13"
+        );
+        assert_eq!(
+            message("true\n[tru\nnull"),
+            "Expected 'true' but input ended at line 2, column 5"
+        );
+        assert_eq!(
+            message("1\n\n[1, x]"),
+            "Unexpected token 'x' at line 3, column 5"
+        );
+        assert_eq!(
+            message("[1,"),
+            "Unexpected end of input at line 1, column 4"
+        );
+    }
+
+    #[test]
+    fn test_collect_errors() {
+        let (value, errors) = parse_lenient_collect("[1, x, 3, {\"a\": tru}, 5]");
+        assert_eq!(value, parse("[1, 3, {}, 5]").ok());
+        assert_eq!(
+            errors,
+            Vec::from([
+                unexpected('x', 4, 1, 5).unwrap_err(),
+                unexpected('}', 19, 1, 20).unwrap_err(),
+            ])
+        );
+
+        let (value, errors) = parse_lenient_collect("{\"a\" 1, b: [2 3], \"c\": \"]\"}");
+        assert_eq!(value, parse("{\"c\": \"]\"}").ok());
+        assert_eq!(
+            errors.iter().map(|error| error.offset).collect::<Vec<_>>(),
+            [5, 8]
+        );
+
+        let (value, errors) = parse_lenient_collect("[1, [2, }");
+        assert_eq!(value, None);
+        assert_eq!(errors, Vec::from([unexpected('}', 8, 1, 9).unwrap_err()]));
+
+        assert_eq!(
+            parse_lenient_collect("[1]"),
+            (parse("[1]").ok(), Vec::new())
+        );
+        assert_eq!(parse_lenient_collect("[1, x, 2 y]").1.len(), 2);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(parse(""), error(ErrorKind::EmptyInput, 0, 1, 1));
+        assert_eq!(parse("   "), error(ErrorKind::EmptyInput, 3, 1, 4));
+        assert_eq!(parse(" \n\t"), error(ErrorKind::EmptyInput, 3, 2, 2));
+        assert_eq!(
+            parse_json5("// nothing\n"),
+            error(ErrorKind::EmptyInput, 11, 2, 1)
+        );
+        assert_eq!(parse("").unwrap_err().to_string(), "Empty input");
+        assert_eq!(parse(" [").unwrap_err().kind, ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_truncated_literals() {
+        assert_eq!(
+            parse("tru"),
+            error(ErrorKind::TruncatedLiteral("true"), 3, 1, 4)
+        );
+        assert_eq!(
+            parse("nul"),
+            error(ErrorKind::TruncatedLiteral("null"), 3, 1, 4)
+        );
+        assert_eq!(
+            parse("[1, fals"),
+            error(ErrorKind::TruncatedLiteral("false"), 8, 1, 9)
+        );
+        assert_eq!(
+            parse("{\"a\": n").unwrap_err().to_string(),
+            "Expected 'null' but input ended"
+        );
+        assert_eq!(parse("trux"), unexpected('x', 3, 1, 4));
+        assert_eq!(parse("nul]"), unexpected(']', 3, 1, 4));
+    }
+
+    #[test]
+    fn test_surrogate_pairs() {
+        assert_eq!(
+            parse("\"\\ud83d\\ude00 \\uD834\\uDD1E\""),
+            Ok(Value::String("\u{1f600} \u{1d11e}".to_owned()))
+        );
+        assert_eq!(
+            parse("\"\\ud83d\""),
+            error(ErrorKind::InvalidUnicode, 2, 1, 3)
+        );
+        assert_eq!(
+            parse("\"\\ud83d\\n\""),
+            error(ErrorKind::InvalidUnicode, 2, 1, 3)
+        );
+        assert_eq!(
+            parse("\"\\ud83d\\u"),
+            error(ErrorKind::UnterminatedString, 0, 1, 1)
+        );
+        assert_eq!(validate("\"\\ud83d\\ude00\""), Ok(()));
+    }
+
+    #[test]
+    fn test_control_escapes() {
+        assert_eq!(
+            parse("\"\\b\\f\""),
+            Ok(Value::String("\u{0008}\u{000C}".to_owned()))
+        );
+        assert_eq!(
+            parse("\"\\bhello\\fworld\""),
+            Ok(Value::String("\u{0008}hello\u{000C}world".to_owned()))
+        );
+        assert_eq!(
+            parse("\"\\bhello\\fworld\"")
+                .unwrap()
+                .as_str()
+                .map(str::as_bytes),
+            Some(&b"\x08hello\x0Cworld"[..])
+        );
+    }
+
+    #[test]
+    fn test_nul_escape() {
+        assert_eq!(parse("\"\\u0000\""), Ok(Value::String("\0".to_owned())));
+        assert_eq!(
+            parse("[\"a\\u0000b\", \"\\u0000\\u0000\"]"),
+            Ok(Value::Array(Vec::from([
+                Value::String("a\0b".to_owned()),
+                Value::String("\0\0".to_owned()),
+            ])))
+        );
+        assert_eq!(
+            parse("{\"\\u0000\": 1}").unwrap().get("\0"),
+            Some(&Value::Integer(1))
+        );
+    }
+
+    #[test]
+    fn test_empty_containers_with_whitespace() {
+        for input in ["{ }", " {\n\t} ", "{\r\n}"] {
+            assert_eq!(parse(input), Ok(Value::Object(Vec::new())), "{:?}", input);
+        }
+        for input in ["[ ]", " [\n\t] ", "[\r\n]"] {
+            assert_eq!(parse(input), Ok(Value::Array(Vec::new())), "{:?}", input);
+        }
+        assert_eq!(
+            parse("{ \"a\" : { } , \"b\" : [ ] }"),
+            Ok(Value::Object(Vec::from([
+                ("a".to_owned(), Value::Object(Vec::new())),
+                ("b".to_owned(), Value::Array(Vec::new())),
+            ])))
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::fmt;
+
+use crate::pointer::{parse_index, unescape};
+use crate::Value;
+
+/// Why [`Value::apply_patch`] failed. `index` is the position of the
+/// offending operation within the patch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    /// The patch is not an array of operations.
+    NotAnArray,
+    /// An operation is not an object with a known `op` and the members that
+    /// operation requires.
+    InvalidOperation { index: usize },
+    /// An operation refers to a location that does not exist, or to a new
+    /// location whose parent does not exist.
+    PathNotFound { index: usize, path: String },
+    /// A `test` operation found a different value at `path`.
+    TestFailed { index: usize, path: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::NotAnArray => write!(f, "Patch is not an array"),
+            PatchError::InvalidOperation { index } => {
+                write!(f, "Invalid operation at index {}", index)
+            }
+            PatchError::PathNotFound { index, path } => {
+                write!(f, "Path '{}' not found by operation {}", path, index)
+            }
+            PatchError::TestFailed { index, path } => {
+                write!(f, "Test of '{}' failed in operation {}", path, index)
+            }
+        }
+    }
+}
+
+impl core::error::Error for PatchError {}
+
+impl Value {
+    /// Applies an [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON
+    /// Patch: an array of `add`, `remove`, `replace`, `move`, `copy` and
+    /// `test` operations, each addressing values by
+    /// [JSON Pointer](Value::pointer).
+    ///
+    /// The operations are applied in order to a copy of `self`, which only
+    /// replaces `self` once all of them have succeeded, so a failed patch
+    /// leaves `self` untouched. `test` compares values as
+    /// [`semantic_eq`](Value::semantic_eq) does.
+    pub fn apply_patch(&mut self, patch: &Value) -> Result<(), PatchError> {
+        let operations = patch.as_array().ok_or(PatchError::NotAnArray)?;
+        let mut document = self.clone();
+
+        for (index, operation) in operations.iter().enumerate() {
+            apply(&mut document, operation, index)?;
+        }
+
+        *self = document;
+        Ok(())
+    }
+}
+
+fn apply(document: &mut Value, operation: &Value, index: usize) -> Result<(), PatchError> {
+    let invalid = || PatchError::InvalidOperation { index };
+    let pointer = |name| {
+        operation
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(invalid)
+    };
+    let value = || operation.get("value").ok_or_else(invalid);
+    let not_found = |path: &str| PatchError::PathNotFound {
+        index,
+        path: path.to_owned(),
+    };
+
+    let path = pointer("path")?;
+    match pointer("op")? {
+        "add" => add(document, path, value()?.clone()).ok_or_else(|| not_found(path)),
+        "remove" => match remove(document, path) {
+            Some(_) => Ok(()),
+            None => Err(not_found(path)),
+        },
+        "replace" => {
+            let value = value()?.clone();
+            *document.pointer_mut(path).ok_or_else(|| not_found(path))? = value;
+            Ok(())
+        }
+        // Moving a value into one of its own children fails, as the parent
+        // of `path` has gone by the time it is added.
+        "move" => {
+            let from = pointer("from")?;
+            let value = remove(document, from).ok_or_else(|| not_found(from))?;
+            add(document, path, value).ok_or_else(|| not_found(path))
+        }
+        "copy" => {
+            let from = pointer("from")?;
+            let value = document.pointer(from).ok_or_else(|| not_found(from))?;
+            add(document, path, value.clone()).ok_or_else(|| not_found(path))
+        }
+        "test" => match document.pointer(path) {
+            Some(actual) if actual.semantic_eq(value()?) => Ok(()),
+            Some(_) => Err(PatchError::TestFailed {
+                index,
+                path: path.to_owned(),
+            }),
+            None => Err(not_found(path)),
+        },
+        _ => Err(invalid()),
+    }
+}
+
+/// Adds `value` as the member or element that `path` names, replacing an
+/// existing member, shifting later elements along, or appending for a final
+/// `-` token. Returns `None` if there is no such place.
+fn add(document: &mut Value, path: &str, value: Value) -> Option<()> {
+    if path.is_empty() {
+        *document = value;
+        return Some(());
+    }
+
+    let (parent, token) = path.rsplit_once('/')?;
+    // An object may have a member named `-`, so only arrays append.
+    if token == "-" && document.pointer(parent).is_some_and(Value::is_array) {
+        return document.pointer_append(path, value).map(|_| ());
+    }
+
+    let parent = document.pointer_mut(parent)?;
+    match parent {
+        Value::Object(_) => {
+            parent.insert(unescape(token), value).ok()?;
+        }
+        Value::Array(array) => {
+            let i = parse_index(token).filter(|&i| i <= array.len())?;
+            array.insert(i, value);
+        }
+        _ => return None,
+    }
+
+    Some(())
+}
+
+/// Removes and returns the member or element that `path` names. The whole
+/// document cannot be removed.
+fn remove(document: &mut Value, path: &str) -> Option<Value> {
+    let (parent, token) = path.rsplit_once('/')?;
+    let parent = document.pointer_mut(parent)?;
+    match parent {
+        Value::Object(_) => parent.remove(&unescape(token)),
+        Value::Array(_) => parent.remove_index(parse_index(token)?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PatchError;
+    use crate::{json, parse, Value};
+
+    fn patched(document: &str, patch: Value) -> Result<String, PatchError> {
+        let mut value = parse(document).unwrap();
+        value.apply_patch(&patch).map(|()| value.serialize())
+    }
+
+    #[test]
+    fn test_add() {
+        let document = "{\"a\": {\"b\": [1, 3]}}";
+
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "add", "path": "/a/c", "value": null }])
+            ),
+            Ok("{\"a\":{\"b\":[1,3],\"c\":null}}".to_owned())
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([
+                    { "op": "add", "path": "/a/b/1", "value": 2 },
+                    { "op": "add", "path": "/a/b/-", "value": 4 },
+                    { "op": "add", "path": "/a/b/0", "value": 0 },
+                ])
+            ),
+            Ok("{\"a\":{\"b\":[0,1,2,3,4]}}".to_owned())
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "add", "path": "/a", "value": [] }])
+            ),
+            Ok("{\"a\":[]}".to_owned())
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "add", "path": "", "value": true }])
+            ),
+            Ok("true".to_owned())
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "add", "path": "/a/b/3", "value": 5 }])
+            ),
+            Err(PatchError::PathNotFound {
+                index: 0,
+                path: "/a/b/3".to_owned()
+            })
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "add", "path": "/x/y", "value": 5 }])
+            ),
+            Err(PatchError::PathNotFound {
+                index: 0,
+                path: "/x/y".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_remove_and_replace() {
+        let document = "{\"a/b\": 1, \"c\": [1, 2, 3], \"d\": {\"e\": \"f\"}}";
+
+        assert_eq!(
+            patched(
+                document,
+                json!([
+                    { "op": "remove", "path": "/a~1b" },
+                    { "op": "remove", "path": "/c/1" },
+                    { "op": "replace", "path": "/d/e", "value": { "g": 2 } },
+                    { "op": "replace", "path": "/c/0", "value": "one" },
+                ])
+            ),
+            Ok("{\"c\":[\"one\",3],\"d\":{\"e\":{\"g\":2}}}".to_owned())
+        );
+        assert_eq!(
+            patched(document, json!([{ "op": "remove", "path": "/c/3" }])),
+            Err(PatchError::PathNotFound {
+                index: 0,
+                path: "/c/3".to_owned()
+            })
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "replace", "path": "/x", "value": 1 }])
+            ),
+            Err(PatchError::PathNotFound {
+                index: 0,
+                path: "/x".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_end_of_array() {
+        let document = "{\"arr\": [1]}";
+
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "add", "path": "/arr/-", "value": 9 }])
+            ),
+            Ok("{\"arr\":[1,9]}".to_owned())
+        );
+        assert_eq!(
+            patched(
+                "{\"obj\": {}}",
+                json!([{ "op": "add", "path": "/obj/-", "value": 9 }])
+            ),
+            Ok("{\"obj\":{\"-\":9}}".to_owned())
+        );
+        for op in [
+            json!({ "op": "replace", "path": "/arr/-", "value": 9 }),
+            json!({ "op": "remove", "path": "/arr/-" }),
+        ] {
+            let mut value = parse(document).unwrap();
+            assert_eq!(
+                value.apply_patch(&Value::Array(Vec::from([op]))),
+                Err(PatchError::PathNotFound {
+                    index: 0,
+                    path: "/arr/-".to_owned()
+                })
+            );
+            assert_eq!(value, parse(document).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_move_and_copy() {
+        let document = "{\"a\": {\"b\": [1, 2]}, \"c\": null}";
+
+        assert_eq!(
+            patched(
+                document,
+                json!([
+                    { "op": "copy", "from": "/a/b", "path": "/c" },
+                    { "op": "move", "from": "/a/b/0", "path": "/a/b/-" },
+                    { "op": "move", "from": "/a", "path": "/z" },
+                ])
+            ),
+            Ok("{\"c\":[1,2],\"z\":{\"b\":[2,1]}}".to_owned())
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "move", "from": "/a", "path": "/a/x" }])
+            ),
+            Err(PatchError::PathNotFound {
+                index: 0,
+                path: "/a/x".to_owned()
+            })
+        );
+        assert_eq!(
+            patched(
+                document,
+                json!([{ "op": "copy", "from": "/x", "path": "/y" }])
+            ),
+            Err(PatchError::PathNotFound {
+                index: 0,
+                path: "/x".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_test() {
+        let document = "{\"a\": [1, {\"b\": 2.0, \"c\": 3}]}";
+
+        assert_eq!(
+            patched(
+                document,
+                json!([
+                    { "op": "test", "path": "/a/1", "value": { "c": 3, "b": 2 } },
+                    { "op": "test", "path": "/a/0", "value": 1 },
+                ])
+            ),
+            Ok("{\"a\":[1,{\"b\":2.0,\"c\":3}]}".to_owned())
+        );
+
+        let mut value = parse(document).unwrap();
+        let error = value
+            .apply_patch(&json!([
+                { "op": "remove", "path": "/a/0" },
+                { "op": "test", "path": "/a/0", "value": 1 },
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            error,
+            PatchError::TestFailed {
+                index: 1,
+                path: "/a/0".to_owned()
+            }
+        );
+        assert_eq!(error.to_string(), "Test of '/a/0' failed in operation 1");
+        assert_eq!(value, parse(document).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_patches() {
+        let document = "{\"a\": 1}";
+
+        assert_eq!(
+            patched(document, json!({ "op": "remove", "path": "/a" })),
+            Err(PatchError::NotAnArray)
+        );
+        for operation in [
+            json!({ "op": "frobnicate", "path": "/a" }),
+            json!({ "path": "/a" }),
+            json!({ "op": "remove" }),
+            json!({ "op": "add", "path": "/b" }),
+            json!({ "op": "move", "path": "/b" }),
+            json!({ "op": "test", "path": 1, "value": 1 }),
+            json!("remove"),
+        ] {
+            assert_eq!(
+                patched(
+                    document,
+                    Value::Array(Vec::from([
+                        json!({ "op": "test", "path": "/a", "value": 1 }),
+                        operation
+                    ]))
+                ),
+                Err(PatchError::InvalidOperation { index: 1 })
+            );
+        }
+    }
+}
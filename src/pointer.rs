@@ -0,0 +1,182 @@
+use crate::Value;
+use alloc::string::String;
+
+impl Value {
+    /// Looks up a value by [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)
+    /// JSON Pointer, e.g. `/users/0/name`.
+    ///
+    /// The empty pointer refers to `self`. Within a token, `~1` stands for
+    /// `/` and `~0` for `~`. Returns `None` for a missing key, an index that is
+    /// out of range or malformed, or a pointer that does not start with `/`.
+    /// The array token `-`, which names the position after the last element,
+    /// is always a miss here.
+    pub fn pointer(&self, path: &str) -> Option<&Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        path.strip_prefix('/')?
+            .split('/')
+            .try_fold(self, |value, token| match value {
+                Value::Object(_) => value.get(&unescape(token)),
+                Value::Array(array) => array.get(parse_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Like [`pointer`](Value::pointer), but returns a mutable reference.
+    ///
+    /// As there, the array token `-` is always a miss, since it names no
+    /// existing element; use [`pointer_append`](Value::pointer_append) to
+    /// add one there.
+    pub fn pointer_mut(&mut self, path: &str) -> Option<&mut Value> {
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        path.strip_prefix('/')?
+            .split('/')
+            .try_fold(self, |value, token| match value {
+                Value::Object(_) => value.get_mut(&unescape(token)),
+                Value::Array(array) => array.get_mut(parse_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Appends `value` to the array that `path` names, where `path` ends in
+    /// the token `-`, e.g. `/users/-`, and returns a reference to it.
+    ///
+    /// Returns `None`, changing nothing and dropping `value`, if `path` does
+    /// not end in `-` or what precedes it is not an array.
+    pub fn pointer_append(&mut self, path: &str, value: Value) -> Option<&mut Value> {
+        match self.pointer_mut(path.strip_suffix("/-")?)? {
+            Value::Array(array) => {
+                array.push(value);
+                array.last_mut()
+            }
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn unescape(token: &str) -> String {
+    // `~1` must be decoded first so that `~01` becomes `~1` rather than `/`.
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Parses an array index token, which must be `0` or have no leading zeros.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || !token.bytes().all(|byte| byte.is_ascii_digit())
+        || (token.len() > 1 && token.starts_with('0'))
+    {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse, Value};
+
+    #[test]
+    fn test_pointer() {
+        let value = parse(
+            "{\"foo\":[\"bar\",\"baz\"],\"\":0,\"a/b\":1,\"c%d\":2,\"e^f\":3,\"g|h\":4,\"i\\\\j\":5,\"k\\\"l\":6,\" \":7,\"m~n\":8,\"~1\":9,\"nested\":{\"list\":[{\"id\":10}]}}",
+        )
+        .unwrap();
+
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/foo"), value.get("foo"));
+        assert_eq!(
+            value.pointer("/foo/0"),
+            Some(&Value::String("bar".to_owned()))
+        );
+        assert_eq!(value.pointer("/"), Some(&Value::Integer(0)));
+        assert_eq!(value.pointer("/a~1b"), Some(&Value::Integer(1)));
+        assert_eq!(value.pointer("/c%d"), Some(&Value::Integer(2)));
+        assert_eq!(value.pointer("/e^f"), Some(&Value::Integer(3)));
+        assert_eq!(value.pointer("/g|h"), Some(&Value::Integer(4)));
+        assert_eq!(value.pointer("/i\\j"), Some(&Value::Integer(5)));
+        assert_eq!(value.pointer("/k\"l"), Some(&Value::Integer(6)));
+        assert_eq!(value.pointer("/ "), Some(&Value::Integer(7)));
+        assert_eq!(value.pointer("/m~0n"), Some(&Value::Integer(8)));
+        assert_eq!(value.pointer("/~01"), Some(&Value::Integer(9)));
+        assert_eq!(
+            value.pointer("/nested/list/0/id"),
+            Some(&Value::Integer(10))
+        );
+    }
+
+    #[test]
+    fn test_pointer_misses() {
+        let value = parse("{\"foo\":[\"bar\",\"baz\"],\"n\":1}").unwrap();
+
+        assert_eq!(value.pointer("foo"), None);
+        assert_eq!(value.pointer("/missing"), None);
+        assert_eq!(value.pointer("/foo/2"), None);
+        assert_eq!(value.pointer("/foo/01"), None);
+        assert_eq!(value.pointer("/foo/+1"), None);
+        assert_eq!(value.pointer("/foo/-1"), None);
+        assert_eq!(value.pointer("/foo/"), None);
+        assert_eq!(value.pointer("/foo/bar"), None);
+        assert_eq!(value.pointer("/n/0"), None);
+        assert_eq!(value.pointer("/foo/0/x"), None);
+        assert_eq!(value.pointer("/foo/-"), None);
+    }
+
+    #[test]
+    fn test_pointer_append() {
+        let mut value = parse("{\"arr\":[1],\"obj\":{\"-\":0},\"n\":1}").unwrap();
+
+        assert_eq!(value.pointer_mut("/arr/-"), None);
+        assert_eq!(
+            value.pointer_append("/arr/-", Value::Integer(2)),
+            Some(&mut Value::Integer(2))
+        );
+        value
+            .pointer_append("/arr/-", Value::from(Vec::new()))
+            .unwrap()
+            .push(Value::Bool(false))
+            .unwrap();
+        assert_eq!(value.pointer_append("/arr/-/-", Value::Null), None);
+        assert_eq!(value.pointer_append("/obj/-", Value::Null), None);
+        assert_eq!(value.pointer_append("/n/-", Value::Null), None);
+        assert_eq!(value.pointer_append("/missing/-", Value::Null), None);
+        assert_eq!(value.pointer_append("/arr/0", Value::Null), None);
+        assert_eq!(value.pointer_append("", Value::Null), None);
+        assert_eq!(
+            value.serialize(),
+            "{\"arr\":[1,2,[false]],\"obj\":{\"-\":0},\"n\":1}"
+        );
+
+        let mut root = parse("[]").unwrap();
+        root.pointer_append("/-", Value::Bool(true)).unwrap();
+        assert_eq!(root.serialize(), "[true]");
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut value = parse("{\"users\":[{\"name\":\"ada\",\"a/b\":[0]}]}").unwrap();
+
+        *value.pointer_mut("/users/0/name").unwrap() = Value::from("grace");
+        value
+            .pointer_mut("/users/0/a~1b")
+            .unwrap()
+            .push(Value::Integer(1))
+            .unwrap();
+        assert_eq!(
+            value.serialize(),
+            "{\"users\":[{\"name\":\"grace\",\"a/b\":[0,1]}]}"
+        );
+
+        assert_eq!(value.pointer_mut("/users/1"), None);
+        assert_eq!(value.pointer_mut("/users/-"), None);
+        assert_eq!(value.pointer_mut("/users/-/name"), None);
+        assert_eq!(value["users"].len(), 1);
+        assert_eq!(value.pointer_mut("users"), None);
+        *value.pointer_mut("").unwrap() = Value::Null;
+        assert_eq!(value, Value::Null);
+    }
+}
@@ -0,0 +1,597 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::Value;
+
+impl Value {
+    /// Serializes the value as compact JSON with no insignificant whitespace.
+    ///
+    /// Non-finite numbers have no JSON representation and are written as
+    /// `null`. Negative zero is written as `-0.0` so that it parses back
+    /// with its sign.
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+        self.serialize_into(&mut out);
+        out
+    }
+
+    /// Appends the value to `out` as compact JSON, exactly as
+    /// [`serialize`](Value::serialize) would, so that one buffer can be
+    /// cleared and reused across many values rather than allocating a new
+    /// `String` for each.
+    pub fn serialize_into(&self, out: &mut String) {
+        write_value(self, None, SerializerOptions::default(), out).unwrap();
+    }
+
+    /// Serializes the value as indented JSON, with each array element and
+    /// object member on its own line, indented two spaces per level.
+    pub fn serialize_pretty(&self) -> String {
+        let mut out = String::new();
+        write_value(self, Some(0), SerializerOptions::default(), &mut out).unwrap();
+        out
+    }
+
+    /// Serializes the value as compact JSON with the members of every object
+    /// sorted by key, so that equal documents produce identical output
+    /// whatever order their keys were parsed in.
+    ///
+    /// Keys are compared by Unicode scalar value. Members with the same key
+    /// keep their relative order.
+    pub fn serialize_canonical(&self) -> String {
+        let mut out = String::new();
+        write_value(
+            self,
+            None,
+            SerializerOptions {
+                sort_keys: true,
+                ..SerializerOptions::default()
+            },
+            &mut out,
+        )
+        .unwrap();
+        out
+    }
+
+    /// Serializes the value as compact JSON that can be embedded in an HTML
+    /// `<script>` element.
+    ///
+    /// `<`, `>` and `&` are written as `\\u003c`, `\\u003e` and `\\u0026`, so
+    /// that no string can close the element or open a comment, and U+2028
+    /// and U+2029 are escaped because older JavaScript engines treat them as
+    /// line breaks.
+    pub fn serialize_html_safe(&self) -> String {
+        self.serialize_with_options(SerializerOptions {
+            html_safe: true,
+            ..SerializerOptions::default()
+        })
+    }
+
+    /// Serializes the value as compact JSON, as configured by `options`.
+    pub fn serialize_with_options(&self, options: SerializerOptions) -> String {
+        let mut out = String::new();
+        write_value(self, None, options, &mut out).unwrap();
+        out
+    }
+
+    /// Serializes the value as compact JSON that is pure ASCII, for
+    /// consumers that cannot handle anything else.
+    ///
+    /// Every non-ASCII char in a string is written as a `\uXXXX` escape, or as
+    /// a surrogate pair of them outside the Basic Multilingual Plane. The
+    /// other serializers write such chars as UTF-8.
+    pub fn serialize_ascii(&self) -> String {
+        let mut out = String::new();
+        write_value(
+            self,
+            None,
+            SerializerOptions {
+                ascii: true,
+                ..SerializerOptions::default()
+            },
+            &mut out,
+        )
+        .unwrap();
+        out
+    }
+}
+
+/// Writes `value` to `writer` as compact JSON, exactly as
+/// [`Value::serialize`] would, without building the whole `String` first.
+///
+/// The output is written in many small pieces, so an unbuffered writer such
+/// as a `File` should be wrapped in a [`BufWriter`](io::BufWriter).
+#[cfg(feature = "std")]
+pub fn to_writer<W: io::Write>(value: &Value, writer: &mut W) -> io::Result<()> {
+    write_io(value, None, writer)
+}
+
+/// Writes `value` to `writer` as indented JSON, exactly as
+/// [`Value::serialize_pretty`] would. See [`to_writer`] about buffering.
+#[cfg(feature = "std")]
+pub fn to_writer_pretty<W: io::Write>(value: &Value, writer: &mut W) -> io::Result<()> {
+    write_io(value, Some(0), writer)
+}
+
+/// Writes the values yielded by `items` to `writer` as a compact JSON array,
+/// each as [`to_writer`] would, so that only one element need be held in
+/// memory at a time. See [`to_writer`] about buffering.
+#[cfg(feature = "std")]
+pub fn write_array_stream<W: io::Write, I: Iterator<Item = Value>>(
+    writer: &mut W,
+    items: I,
+) -> io::Result<()> {
+    writer.write_all(b"[")?;
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        to_writer(&item, writer)?;
+    }
+    writer.write_all(b"]")
+}
+
+#[cfg(feature = "std")]
+fn write_io<W: io::Write>(value: &Value, indent: Option<usize>, writer: &mut W) -> io::Result<()> {
+    let mut adapter = IoAdapter {
+        writer,
+        error: None,
+    };
+
+    match write_value(value, indent, SerializerOptions::default(), &mut adapter) {
+        Ok(()) => Ok(()),
+        // `fmt::Error` carries nothing, so the cause is kept aside.
+        Err(fmt::Error) => Err(adapter
+            .error
+            .unwrap_or_else(|| io::Error::other("formatter error"))),
+    }
+}
+
+/// Lets the serializer, which writes to a [`fmt::Write`], drive an
+/// [`io::Write`].
+#[cfg(feature = "std")]
+struct IoAdapter<'a, W> {
+    writer: &'a mut W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Settings that alter how a value is serialized, for
+/// [`Value::serialize_with_options`].
+///
+/// The default matches [`Value::serialize`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerializerOptions {
+    /// Write object members in key order, as
+    /// [`serialize_canonical`](Value::serialize_canonical) does, rather than
+    /// source order.
+    pub sort_keys: bool,
+    /// Escape every non-ASCII char, as
+    /// [`serialize_ascii`](Value::serialize_ascii) does.
+    pub ascii: bool,
+    /// Write `/` as `\\/`, as PHP's `json_encode` does by default, so that
+    /// output embedded in an HTML `<script>` element cannot close it with
+    /// `</script>`.
+    pub escape_forward_slash: bool,
+    /// Escape `<`, `>`, `&` and the line and paragraph separators U+2028 and
+    /// U+2029, as [`serialize_html_safe`](Value::serialize_html_safe) does.
+    pub html_safe: bool,
+}
+
+/// Writes `value` compactly if `indent` is `None`, or pretty-printed as if
+/// nested `indent` levels deep otherwise.
+fn write_value<W: Write>(
+    value: &Value,
+    indent: Option<usize>,
+    options: SerializerOptions,
+    out: &mut W,
+) -> fmt::Result {
+    let inner = indent.map(|level| level + 1);
+
+    match value {
+        Value::Null => out.write_str("null"),
+        Value::Bool(true) => out.write_str("true"),
+        Value::Bool(false) => out.write_str("false"),
+        Value::String(string) => write_string(string, options, out),
+        Value::Integer(integer) => write!(out, "{}", integer),
+        Value::Number(number) => write_number(*number, out),
+        Value::RawNumber(raw) => out.write_str(raw),
+        Value::Array(array) => {
+            out.write_char('[')?;
+            for (i, value) in array.iter().enumerate() {
+                if i != 0 {
+                    out.write_char(',')?;
+                }
+                write_newline(inner, out)?;
+                write_value(value, inner, options, out)?;
+            }
+            if !array.is_empty() {
+                write_newline(indent, out)?;
+            }
+            out.write_char(']')
+        }
+        Value::Object(key_values) => {
+            out.write_char('{')?;
+            if options.sort_keys {
+                let mut sorted: Vec<_> = key_values.iter().collect();
+                // UTF-8 byte order is the same as scalar value order.
+                sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+                write_members(sorted.into_iter(), indent, options, out)?;
+            } else {
+                write_members(key_values.iter(), indent, options, out)?;
+            }
+            if !key_values.is_empty() {
+                write_newline(indent, out)?;
+            }
+            out.write_char('}')
+        }
+    }
+}
+
+fn write_members<'a, W: Write>(
+    members: impl Iterator<Item = &'a (String, Value)>,
+    indent: Option<usize>,
+    options: SerializerOptions,
+    out: &mut W,
+) -> fmt::Result {
+    let inner = indent.map(|level| level + 1);
+
+    for (i, (key, value)) in members.enumerate() {
+        if i != 0 {
+            out.write_char(',')?;
+        }
+        write_newline(inner, out)?;
+        write_string(key, options, out)?;
+        out.write_char(':')?;
+        if indent.is_some() {
+            out.write_char(' ')?;
+        }
+        write_value(value, inner, options, out)?;
+    }
+
+    Ok(())
+}
+
+fn write_newline<W: Write>(indent: Option<usize>, out: &mut W) -> fmt::Result {
+    if let Some(level) = indent {
+        out.write_char('\n')?;
+        for _ in 0..level {
+            out.write_str("  ")?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_number<W: Write>(number: f64, out: &mut W) -> fmt::Result {
+    if number.is_finite() {
+        // `Debug` is the shortest representation that round-trips, and
+        // switches to exponent notation for very large and small magnitudes.
+        write!(out, "{:?}", number)
+    } else {
+        out.write_str("null")
+    }
+}
+
+/// Escapes `string` for use as the contents of a JSON string, without adding
+/// the surrounding quotes.
+///
+/// Quotes, backslashes and control chars are escaped exactly as
+/// [`Value::serialize`] escapes them; everything else is left as it is.
+pub fn escape_string(string: &str) -> String {
+    let mut out = String::with_capacity(string.len());
+    write_escaped(string, SerializerOptions::default(), &mut out).unwrap();
+    out
+}
+
+fn write_string<W: Write>(string: &str, options: SerializerOptions, out: &mut W) -> fmt::Result {
+    out.write_char('"')?;
+    write_escaped(string, options, out)?;
+    out.write_char('"')
+}
+
+fn write_escaped<W: Write>(string: &str, options: SerializerOptions, out: &mut W) -> fmt::Result {
+    // Runs of chars that need no escaping are written in one go.
+    let mut start = 0;
+    for (i, ch) in string.char_indices() {
+        let escape = match ch {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            '\u{0008}' => "\\b",
+            '\u{000C}' => "\\f",
+            '\u{0000}'..='\u{001F}' => "",
+            '/' if options.escape_forward_slash => "\\/",
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' if options.html_safe => "",
+            _ if options.ascii && !ch.is_ascii() => "",
+            _ => continue,
+        };
+        out.write_str(&string[start..i])?;
+        match escape {
+            // Astral chars take two units, as a UTF-16 surrogate pair.
+            "" => {
+                for unit in ch.encode_utf16(&mut [0; 2]) {
+                    write!(out, "\\u{:04x}", unit)?;
+                }
+            }
+            escape => out.write_str(escape)?,
+        }
+        start = i + ch.len_utf8();
+    }
+    out.write_str(&string[start..])
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        escape_string, parse, parse_bytes, to_writer, to_writer_pretty, unescape_string,
+        write_array_stream, ErrorKind, SerializerOptions, Value,
+    };
+
+    fn round_trip(input: &str) {
+        let value = parse(input).unwrap();
+        let serialized = value.serialize();
+        assert_eq!(parse(&serialized), Ok(value), "{}", serialized);
+    }
+
+    fn round_trip_pretty(input: &str) {
+        let value = parse(input).unwrap();
+        assert_eq!(parse(&value.serialize_pretty()), Ok(value));
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(Value::Null.serialize(), "null");
+        assert_eq!(Value::Bool(true).serialize(), "true");
+        assert_eq!(Value::Bool(false).serialize(), "false");
+        assert_eq!(Value::Integer(-7).serialize(), "-7");
+        assert_eq!(Value::Number(1.5).serialize(), "1.5");
+        assert_eq!(Value::Number(1.0).serialize(), "1.0");
+        assert_eq!(Value::Number(f64::NAN).serialize(), "null");
+        assert_eq!(
+            Value::RawNumber("1.000000000000000001".to_owned()).serialize(),
+            "1.000000000000000001"
+        );
+        assert_eq!(
+            Value::String("a\"b\\c\n\u{0001}\u{0008}".to_owned()).serialize(),
+            "\"a\\\"b\\\\c\\n\\u0001\\b\""
+        );
+        assert_eq!(
+            parse("  { \"a\" : [ 1 , true , null ] , \"b\" : { } }  ")
+                .unwrap()
+                .serialize(),
+            "{\"a\":[1,true,null],\"b\":{}}"
+        );
+    }
+
+    #[test]
+    fn test_serialize_into() {
+        let mut buffer = String::from("> ");
+        parse("[1, {\"a\": null}]")
+            .unwrap()
+            .serialize_into(&mut buffer);
+        assert_eq!(buffer, "> [1,{\"a\":null}]");
+
+        buffer.clear();
+        let capacity = buffer.capacity();
+        Value::from("b").serialize_into(&mut buffer);
+        assert_eq!(buffer, "\"b\"");
+        assert_eq!(buffer.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_serialize_pretty() {
+        assert_eq!(Value::Null.serialize_pretty(), "null");
+        assert_eq!(parse("[]").unwrap().serialize_pretty(), "[]");
+        assert_eq!(
+            parse("{\"a\":[1,{\"b\":null},[]],\"c\":{},\"d\":\"e\"}")
+                .unwrap()
+                .serialize_pretty(),
+            "{\n  \"a\": [\n    1,\n    {\n      \"b\": null\n    },\n    []\n  ],\n  \"c\": {},\n  \"d\": \"e\"\n}"
+        );
+        round_trip_pretty("{\"a\": [1, 2.5, \"\\n\"], \"b\": {\"c\": [[]]}}");
+    }
+
+    #[test]
+    fn test_serialize_canonical() {
+        let left =
+            parse("{\"b\": [{\"z\": 1, \"y\": 2}], \"a\": null, \"\u{e9}\": 0, \"B\": 3}").unwrap();
+        let right =
+            parse("{\"B\": 3, \"\u{e9}\": 0, \"a\": null, \"b\": [{\"y\": 2, \"z\": 1}]}").unwrap();
+
+        assert_ne!(left.serialize(), right.serialize());
+        assert_eq!(left.serialize_canonical(), right.serialize_canonical());
+        assert_eq!(
+            left.serialize_canonical(),
+            "{\"B\":3,\"a\":null,\"b\":[{\"y\":2,\"z\":1}],\"\u{e9}\":0}"
+        );
+        assert_eq!(
+            parse("{\"b\": 1, \"a\": 2, \"b\": 0}")
+                .unwrap()
+                .serialize_canonical(),
+            "{\"a\":2,\"b\":1,\"b\":0}"
+        );
+        assert_eq!(parse("[3, 1, 2]").unwrap().serialize_canonical(), "[3,1,2]");
+    }
+
+    #[test]
+    fn test_serialize_numbers() {
+        for (input, expected) in [("0.1", "0.1"), ("1e300", "1e300"), ("-0.0", "-0.0")] {
+            let value = parse(input).unwrap();
+            assert_eq!(value.serialize(), expected);
+
+            match (parse(&value.serialize()), value) {
+                (Ok(Value::Number(after)), Value::Number(before)) => {
+                    assert_eq!(after.to_bits(), before.to_bits(), "{}", input)
+                }
+                other => panic!("{:?}", other),
+            }
+        }
+
+        assert_eq!(Value::Number(1e-7).serialize(), "1e-7");
+        assert_eq!(
+            Value::Number(0.30000000000000004).serialize(),
+            "0.30000000000000004"
+        );
+        assert_eq!(Value::Integer(i64::MIN).serialize(), "-9223372036854775808");
+        assert_eq!(Value::Number(0.0).serialize(), "0.0");
+        assert_eq!(parse("-0").unwrap().serialize(), "-0.0");
+        assert_eq!(parse("[-0e3]").unwrap().serialize(), "[-0.0]");
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        round_trip("null");
+        round_trip("\"\\b\\f\\n\\r\\t\\\"\\\\\\/\\u0000\\u001f\\u00e9\"");
+        round_trip("[0, -0, 1.5, -2.25e-10, 6.02e23, 1e300]");
+        round_trip("{\"a\": {\"b\": [1, [2, [3]]]}, \"c\": \"d\", \"c\": false}");
+        round_trip("[{}, [], \"\", {\"\": []}]");
+    }
+
+    #[test]
+    fn test_serialize_ascii() {
+        let value = Value::from("caf\u{e9} \u{1d11e}\n");
+
+        assert_eq!(value.serialize(), "\"caf\u{e9} \u{1d11e}\\n\"");
+        assert_eq!(value.serialize_ascii(), "\"caf\\u00e9 \\ud834\\udd1e\\n\"");
+        assert_eq!(parse(&value.serialize_ascii()), Ok(value));
+
+        let value = parse("{\"\u{e9}\": [\"\u{1f600}\", 1]}").unwrap();
+        assert_eq!(
+            value.serialize_ascii(),
+            "{\"\\u00e9\":[\"\\ud83d\\ude00\",1]}"
+        );
+        assert_eq!(parse(&value.serialize_ascii()), Ok(value));
+    }
+
+    #[test]
+    fn test_escape_forward_slash() {
+        let value = parse("{\"</script>\": \"a/b\\/c\"}").unwrap();
+        let escaped = SerializerOptions {
+            escape_forward_slash: true,
+            ..SerializerOptions::default()
+        };
+
+        assert_eq!(value.serialize(), "{\"</script>\":\"a/b/c\"}");
+        assert_eq!(
+            value.serialize_with_options(SerializerOptions::default()),
+            value.serialize()
+        );
+        assert_eq!(
+            value.serialize_with_options(escaped),
+            "{\"<\\/script>\":\"a\\/b\\/c\"}"
+        );
+        assert_eq!(parse(&value.serialize_with_options(escaped)), Ok(value));
+    }
+
+    #[test]
+    fn test_serialize_html_safe() {
+        let value = parse("[\"</script><!-- a & b\", \"\\u2028\\u2029\", \"caf\u{e9}\"]").unwrap();
+
+        assert_eq!(
+            value.serialize_html_safe(),
+            "[\"\\u003c/script\\u003e\\u003c!-- a \\u0026 b\",\"\\u2028\\u2029\",\"caf\u{e9}\"]"
+        );
+        assert!(!value.serialize_html_safe().contains("</"));
+        assert_eq!(parse(&value.serialize_html_safe()), Ok(value));
+    }
+
+    #[test]
+    fn test_to_writer() {
+        let value = parse("{\"a\": [1, 2.5, \"\\u00e9\\n\"], \"b\": {}, \"c\": null}").unwrap();
+
+        let mut buffer = Vec::new();
+        to_writer(&value, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer.clone()).unwrap(),
+            value.serialize()
+        );
+        assert_eq!(parse_bytes(&buffer).as_ref(), Ok(&value));
+
+        let mut buffer = Vec::new();
+        to_writer_pretty(&value, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer.clone()).unwrap(),
+            value.serialize_pretty()
+        );
+        assert_eq!(parse_bytes(&buffer).as_ref(), Ok(&value));
+    }
+
+    #[test]
+    fn test_to_writer_error() {
+        let value = parse("[1, 2, 3]").unwrap();
+        let mut buffer = [0; 4];
+
+        let error = to_writer(&value, &mut &mut buffer[..]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(&buffer, b"[1,2");
+    }
+
+    #[test]
+    fn test_escape_string() {
+        for (raw, escaped) in [
+            ("", ""),
+            ("plain caf\u{e9}", "plain caf\u{e9}"),
+            ("a\"b\\c/", "a\\\"b\\\\c/"),
+            (
+                "\n\r\t\u{0008}\u{000C}\u{0000}\u{001f}",
+                "\\n\\r\\t\\b\\f\\u0000\\u001f",
+            ),
+            ("\u{1f600}", "\u{1f600}"),
+        ] {
+            assert_eq!(escape_string(raw), escaped);
+            assert_eq!(unescape_string(escaped).as_deref(), Ok(raw));
+        }
+
+        assert_eq!(
+            unescape_string("\\u00e9\\ud83d\\ude00\\/").as_deref(),
+            Ok("\u{e9}\u{1f600}/")
+        );
+    }
+
+    #[test]
+    fn test_unescape_string_errors() {
+        for (fragment, kind, offset) in [
+            ("a\\x", ErrorKind::InvalidEscape('x'), 2),
+            ("a\"b", ErrorKind::UnexpectedToken('"'), 1),
+            ("\\ud83dx", ErrorKind::InvalidUnicode, 1),
+            ("\\ud83d", ErrorKind::UnexpectedEof, 6),
+            ("\\ude00", ErrorKind::InvalidUnicode, 1),
+            ("ab\\", ErrorKind::UnexpectedEof, 3),
+            ("\\u00", ErrorKind::UnexpectedEof, 4),
+        ] {
+            let error = unescape_string(fragment).unwrap_err();
+            assert_eq!((error.kind, error.offset), (kind, offset), "{}", fragment);
+        }
+    }
+
+    #[test]
+    fn test_write_array_stream() {
+        let mut buffer = Vec::new();
+        write_array_stream(&mut buffer, (0..1000).map(Value::Integer)).unwrap();
+
+        let value = parse_bytes(&buffer).unwrap();
+        assert_eq!(value.len(), 1000);
+        assert!(value
+            .iter()
+            .zip(0..)
+            .all(|(item, i)| *item == Value::Integer(i)));
+
+        let mut buffer = Vec::new();
+        write_array_stream(&mut buffer, core::iter::empty()).unwrap();
+        assert_eq!(buffer, b"[]");
+    }
+}
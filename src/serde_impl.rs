@@ -0,0 +1,140 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Error, Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::Value;
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::String(string) => serializer.serialize_str(string),
+            Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::Number(number) => serializer.serialize_f64(*number),
+            Value::RawNumber(raw) => match raw.parse::<i64>() {
+                Ok(integer) => serializer.serialize_i64(integer),
+                Err(_) => serializer.serialize_f64(raw.parse().map_err(S::Error::custom)?),
+            },
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for value in array {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Value::Object(key_values) => {
+                let mut map = serializer.serialize_map(Some(key_values.len()))?;
+                for (key, value) in key_values {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Integer(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        // Mirror the parser: integers too large for an `i64` become floats.
+        Ok(match i64::try_from(value) {
+            Ok(value) => Value::Integer(value),
+            Err(_) => Value::Number(value as f64),
+        })
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::Number(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            array.push(value);
+        }
+
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut key_values = Vec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(entry) = map.next_entry()? {
+            key_values.push(entry);
+        }
+
+        Ok(Value::Object(key_values))
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse, Value};
+
+    #[test]
+    fn test_serde_round_trip() {
+        let input =
+            "{\"a\":[1,-2.5,true,false,null],\"b\":{\"c\":\"d\\n\"},\"e\":18446744073709551615}";
+        let value = parse(input).unwrap();
+
+        let serialized = serde_json::to_string(&value).unwrap();
+        assert_eq!(parse(&serialized), Ok(value));
+
+        let deserialized: Value = serde_json::from_str(input).unwrap();
+        assert_eq!(Ok(deserialized), parse(input));
+    }
+
+    #[test]
+    fn test_serde_json_value() {
+        let value = parse("{\"a\":[1,2.5,\"x\"],\"b\":null}").unwrap();
+        let json = serde_json::to_value(&value).unwrap();
+
+        assert_eq!(json, serde_json::json!({"a": [1, 2.5, "x"], "b": null}));
+        assert_eq!(serde_json::from_value::<Value>(json).unwrap(), value);
+    }
+}
@@ -0,0 +1,174 @@
+use alloc::borrow::Cow;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::parser::{Builder, Parser, Scalar};
+use crate::{ParseError, ParserOptions, Value};
+
+/// A parsed JSON value along with the byte range of the input it was parsed
+/// from, as returned by [`parse_spanned`].
+///
+/// The span covers the value itself, such as the quotes around a string or
+/// the brackets around an array, but not any whitespace on either side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub span: Range<usize>,
+    pub node: SpannedNode,
+}
+
+/// The contents of a [`Spanned`] value, whose elements and member values are
+/// spanned in turn.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpannedNode {
+    Null,
+    Bool(bool),
+    String(String),
+    Integer(i64),
+    Number(f64),
+    RawNumber(String),
+    Array(Vec<Spanned>),
+    Object(Vec<(String, Spanned)>),
+}
+
+impl Spanned {
+    /// Looks up an object member like [`Value::get`].
+    pub fn get(&self, key: &str) -> Option<&Spanned> {
+        match &self.node {
+            SpannedNode::Object(key_values) => key_values
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Looks up an array element like [`Value::get_index`].
+    pub fn get_index(&self, i: usize) -> Option<&Spanned> {
+        match &self.node {
+            SpannedNode::Array(array) => array.get(i),
+            _ => None,
+        }
+    }
+
+    /// Drops the spans to produce a plain [`Value`].
+    pub fn into_value(self) -> Value {
+        match self.node {
+            SpannedNode::Null => Value::Null,
+            SpannedNode::Bool(value) => Value::Bool(value),
+            SpannedNode::String(string) => Value::String(string),
+            SpannedNode::Integer(integer) => Value::Integer(integer),
+            SpannedNode::Number(number) => Value::Number(number),
+            SpannedNode::RawNumber(raw) => Value::RawNumber(raw),
+            SpannedNode::Array(array) => {
+                Value::Array(array.into_iter().map(Spanned::into_value).collect())
+            }
+            SpannedNode::Object(key_values) => Value::Object(
+                key_values
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct SpannedBuilder;
+
+impl SpannedBuilder {
+    /// Wraps `node` with a placeholder span, which the parser fills in
+    /// through [`Builder::span`] once the value ends.
+    fn unspanned(node: SpannedNode) -> Spanned {
+        Spanned { span: 0..0, node }
+    }
+}
+
+impl<'a> Builder<'a> for SpannedBuilder {
+    type Value = Spanned;
+    type Array = Vec<Spanned>;
+    type Object = Vec<(String, Spanned)>;
+
+    const RECORD_SPANS: bool = true;
+
+    fn scalar(&mut self, scalar: Scalar<'a>) -> Spanned {
+        SpannedBuilder::unspanned(match scalar {
+            Scalar::Null => SpannedNode::Null,
+            Scalar::Bool(value) => SpannedNode::Bool(value),
+            Scalar::String(string) => SpannedNode::String(string.into_owned()),
+            Scalar::Integer(integer) => SpannedNode::Integer(integer),
+            Scalar::Number(number) => SpannedNode::Number(number),
+            Scalar::RawNumber(raw) => SpannedNode::RawNumber(raw.to_owned()),
+        })
+    }
+
+    fn array(&mut self) -> Self::Array {
+        Vec::new()
+    }
+
+    fn push(&mut self, array: &mut Self::Array, value: Spanned) {
+        array.push(value);
+    }
+
+    fn finish_array(&mut self, array: Self::Array) -> Spanned {
+        SpannedBuilder::unspanned(SpannedNode::Array(array))
+    }
+
+    fn object(&mut self) -> Self::Object {
+        Vec::new()
+    }
+
+    fn insert(&mut self, object: &mut Self::Object, key: Cow<'a, str>, value: Spanned) {
+        object.push((key.into_owned(), value));
+    }
+
+    fn finish_object(&mut self, object: Self::Object) -> Spanned {
+        SpannedBuilder::unspanned(SpannedNode::Object(object))
+    }
+
+    fn span(&mut self, value: Spanned, span: Range<usize>) -> Spanned {
+        Spanned { span, ..value }
+    }
+}
+
+/// Parses a complete JSON document, recording the byte range that each value
+/// occupies in `string`, for tools that map values back to their source.
+///
+/// Plain [`parse`](crate::parse) records no spans and pays nothing for them.
+pub fn parse_spanned(string: &str) -> Result<Spanned, ParseError> {
+    Parser::new(string, ParserOptions::default()).parse(&mut SpannedBuilder)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_spanned, SpannedNode};
+    use crate::parse;
+
+    #[test]
+    fn test_parse_spanned() {
+        let input = "{\"a\": [1, {\"b\": \"c\\n\"}], \"d\": null }";
+        let spanned = parse_spanned(input).unwrap();
+
+        assert_eq!(spanned.span, 0..input.len());
+        let a = spanned.get("a").unwrap();
+        assert_eq!(&input[a.span.clone()], "[1, {\"b\": \"c\\n\"}]");
+        assert_eq!(a.get_index(0).unwrap().span, 7..8);
+
+        let b = a.get_index(1).unwrap().get("b").unwrap();
+        assert_eq!(b.span, 16..21);
+        assert_eq!(b.node, SpannedNode::String("c\n".to_owned()));
+        assert_eq!(&input[spanned.get("d").unwrap().span.clone()], "null");
+
+        assert_eq!(Ok(spanned.into_value()), parse(input));
+    }
+
+    #[test]
+    fn test_parse_spanned_whitespace() {
+        let spanned = parse_spanned("  [ -1.5e3 ,true ]\n").unwrap();
+
+        assert_eq!(spanned.span, 2..18);
+        assert_eq!(spanned.get_index(0).unwrap().span, 4..10);
+        assert_eq!(spanned.get_index(1).unwrap().span, 12..16);
+        assert_eq!(parse_spanned("[1,]").err(), parse("[1,]").err());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::parser::{Parser, Scalar};
+use crate::{ErrorKind, ParseError, ParserOptions};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A single token of a document, as produced by [`StreamParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    StartObject,
+    /// An object member's key. The member's value follows as its own events.
+    Key(String),
+    EndObject,
+    StartArray,
+    EndArray,
+    Null,
+    Bool(bool),
+    String(String),
+    Integer(i64),
+    Number(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Container {
+    Array,
+    Object,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// A value is expected next.
+    Value,
+    /// Just after `[`: either the first element or `]`.
+    FirstElement,
+    /// Just after `{`: either the first key or `}`.
+    FirstMember,
+    /// Just after `,` in an object: a key is expected next.
+    Key,
+    /// A value just ended: a separator, a closing bracket, or EOF follows.
+    AfterValue,
+    /// The document ended or an error was returned.
+    Done,
+}
+
+/// A pull parser that yields a document as a sequence of [`Event`]s.
+///
+/// Only the stack of open arrays and objects is kept in memory, so documents
+/// of any size can be processed without building a [`Value`](crate::Value)
+/// tree. Each event is only produced once its input has been validated; after
+/// the first error the iterator is exhausted.
+pub struct StreamParser<'a> {
+    parser: Parser<'a>,
+    stack: Vec<Container>,
+    state: State,
+}
+
+impl<'a> StreamParser<'a> {
+    /// Creates a stream over a complete document.
+    pub fn new(string: &'a str) -> Self {
+        StreamParser {
+            parser: Parser::new(string, ParserOptions::default()),
+            stack: Vec::new(),
+            state: State::Value,
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event>, ParseError> {
+        self.parser.consume_whitespace()?;
+
+        match self.state {
+            State::Value if self.stack.is_empty() && self.parser.chars.peek().is_none() => {
+                let offset = self.parser.offset();
+                Err(self.parser.error_at(ErrorKind::EmptyInput, offset))
+            }
+            State::Value => self.parse_value().map(Some),
+            State::FirstElement => {
+                if self.parser.consume(']') {
+                    self.close()
+                } else {
+                    self.parse_value().map(Some)
+                }
+            }
+            State::FirstMember => {
+                if self.parser.consume('}') {
+                    self.close()
+                } else {
+                    self.parse_key().map(Some)
+                }
+            }
+            State::Key => self.parse_key().map(Some),
+            State::AfterValue => match self.stack.last() {
+                None => match self.parser.chars.peek() {
+                    None => {
+                        self.state = State::Done;
+                        Ok(None)
+                    }
+                    Some(&data) => self.parser.error(Some(data)),
+                },
+                Some(Container::Array) => {
+                    if self.parser.consume(',') {
+                        self.state = State::Value;
+                        self.next_event()
+                    } else {
+                        self.parser.try_consume(']', "',' or ']'")?;
+                        self.close()
+                    }
+                }
+                Some(Container::Object) => {
+                    if self.parser.consume(',') {
+                        self.state = State::Key;
+                        self.next_event()
+                    } else {
+                        self.parser.try_consume('}', "',' or '}'")?;
+                        self.close()
+                    }
+                }
+            },
+            State::Done => Ok(None),
+        }
+    }
+
+    fn close(&mut self) -> Result<Option<Event>, ParseError> {
+        self.state = State::AfterValue;
+
+        Ok(match self.stack.pop() {
+            Some(Container::Array) => Some(Event::EndArray),
+            _ => Some(Event::EndObject),
+        })
+    }
+
+    fn parse_key(&mut self) -> Result<Event, ParseError> {
+        if !self.parser.consume('"') {
+            self.parser.reject_comma("key")?;
+            let data = self.parser.chars.peek().copied();
+            return self.parser.error(data);
+        }
+        let key = self.parser.parse_string(true)?.into_owned();
+
+        self.parser.consume_whitespace()?;
+        self.parser.try_consume(':', "':'")?;
+        self.state = State::Value;
+
+        Ok(Event::Key(key))
+    }
+
+    fn parse_value(&mut self) -> Result<Event, ParseError> {
+        self.parser.reject_comma("value")?;
+        let scalar = match self.parser.chars.next() {
+            Some((_, '[')) => {
+                self.stack.push(Container::Array);
+                self.state = State::FirstElement;
+                return Ok(Event::StartArray);
+            }
+            Some((_, '{')) => {
+                self.stack.push(Container::Object);
+                self.state = State::FirstMember;
+                return Ok(Event::StartObject);
+            }
+            data => self.parser.parse_scalar(data, true),
+        }?;
+
+        self.state = State::AfterValue;
+
+        Ok(match scalar {
+            Scalar::Null => Event::Null,
+            Scalar::Bool(value) => Event::Bool(value),
+            Scalar::String(string) => Event::String(string.into_owned()),
+            Scalar::Integer(integer) => Event::Integer(integer),
+            Scalar::Number(number) => Event::Number(number),
+            // `StreamParser::new` always parses with the default options,
+            // which never keep numbers raw. Threading options through would
+            // need an event that carries the raw text.
+            Scalar::RawNumber(_) => unreachable!("streams never keep raw numbers"),
+        })
+    }
+}
+
+impl Iterator for StreamParser<'_> {
+    type Item = Result<Event, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_event() {
+            Ok(event) => event.map(Ok),
+            Err(error) => {
+                self.state = State::Done;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Event::{self, *};
+    use super::StreamParser;
+    use crate::{parse, ErrorKind};
+
+    fn events(input: &str) -> Vec<Event> {
+        StreamParser::new(input).map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn test_events() {
+        assert_eq!(
+            events(" { \"a\" : [1, 2.5, {\"b\": null}, []], \"c\": {}, \"d\": \"e\", \"f\": [true, false] } "),
+            Vec::from([
+                StartObject,
+                Key("a".to_owned()),
+                StartArray,
+                Integer(1),
+                Number(2.5),
+                StartObject,
+                Key("b".to_owned()),
+                Null,
+                EndObject,
+                StartArray,
+                EndArray,
+                EndArray,
+                Key("c".to_owned()),
+                StartObject,
+                EndObject,
+                Key("d".to_owned()),
+                String("e".to_owned()),
+                Key("f".to_owned()),
+                StartArray,
+                Bool(true),
+                Bool(false),
+                EndArray,
+                EndObject,
+            ])
+        );
+        assert_eq!(events("\"top\""), Vec::from([String("top".to_owned())]));
+        assert_eq!(events("[[[]]]").len(), 6);
+    }
+
+    #[test]
+    fn test_event_errors() {
+        for input in [
+            "[+1]",
+            "[1,]",
+            "{\"a\":1,}",
+            "[1 2]",
+            "{\"a\" 1}",
+            "[",
+            "1 2",
+            "",
+            "{1:2}",
+            "[,1]",
+            "[1,,2]",
+            "{,}",
+        ] {
+            let mut stream = StreamParser::new(input);
+            let error = stream.find_map(Result::err);
+
+            assert_eq!(error, parse(input).err(), "{}", input);
+            assert_eq!(stream.next(), None);
+        }
+
+        let mut stream = StreamParser::new("[1, x]");
+        assert_eq!(stream.next(), Some(Ok(StartArray)));
+        assert_eq!(stream.next(), Some(Ok(Integer(1))));
+        assert_eq!(
+            stream.next().unwrap().map_err(|error| error.kind),
+            Err(ErrorKind::UnexpectedToken('x'))
+        );
+        assert_eq!(stream.next(), None);
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::borrow::Cow;
+
+use crate::parser::{Builder, Parser, Scalar};
+use crate::{ParseError, ParserOptions};
+
+/// Walks the grammar without building anything.
+struct Validator;
+
+impl<'a> Builder<'a> for Validator {
+    type Value = ();
+    type Array = ();
+    type Object = ();
+
+    const DECODE_STRINGS: bool = false;
+
+    fn scalar(&mut self, _: Scalar<'a>) {}
+
+    fn array(&mut self) {}
+
+    fn push(&mut self, _: &mut (), _: ()) {}
+
+    fn finish_array(&mut self, _: ()) {}
+
+    fn object(&mut self) {}
+
+    fn insert(&mut self, _: &mut (), _: Cow<'a, str>, _: ()) {}
+
+    fn finish_object(&mut self, _: ()) {}
+}
+
+/// Checks that `string` is a valid document without building a
+/// [`Value`](crate::Value).
+///
+/// Accepts and rejects exactly the same inputs as [`parse`](crate::parse),
+/// with the same errors, but allocates nothing along the way.
+pub fn validate(string: &str) -> Result<(), ParseError> {
+    Parser::new(string, ParserOptions::default()).parse(&mut Validator)
+}
+
+#[cfg(test)]
+mod test {
+    use super::validate;
+    use crate::parse;
+
+    #[test]
+    fn test_validate() {
+        for input in [
+            "null",
+            " true ",
+            "false",
+            "0",
+            "-0",
+            "-12.5e+3",
+            "1E-2",
+            "0.25",
+            "0e5",
+            "\"\"",
+            "\"plain\"",
+            "\"\\\"\\\\\\/\\b\\f\\n\\r\\t\\u00e9\"",
+            "[]",
+            "{}",
+            "[1, [2, [3]], {\"a\": {\"b\": []}}]",
+            "{\"k\\u0065y\": \"v\", \"key\": null}",
+        ] {
+            assert_eq!(validate(input), Ok(()), "{}", input);
+            assert!(parse(input).is_ok(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_validate_errors() {
+        for input in [
+            "",
+            "nul",
+            "truex",
+            "01",
+            "-",
+            "+1",
+            "1.",
+            "1e",
+            ".5",
+            "\"unterminated",
+            "\"\\x\"",
+            "\"\\u12g4\"",
+            "\"\\ud800\"",
+            "[1,]",
+            "[1 2]",
+            "{\"a\":1,}",
+            "{\"a\" 1}",
+            "{1:2}",
+            "[",
+            "1 2",
+            "// comment\n1",
+            &"[".repeat(200),
+        ] {
+            assert_eq!(validate(input).err(), parse(input).err(), "{}", input);
+            assert!(validate(input).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,1183 @@
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem;
+use core::ops::Index;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{parse, ParseError};
+
+/// A parsed JSON value.
+///
+/// Object members are kept in source order, duplicates included.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    String(String),
+    /// A number written without a fraction or exponent that fits in an `i64`.
+    Integer(i64),
+    /// Any other number. Negative zero keeps its sign, through both parsing
+    /// and [`serialize`](Value::serialize).
+    Number(f64),
+    /// A number literal exactly as written, kept in place of
+    /// [`Integer`](Value::Integer) and [`Number`](Value::Number) when
+    /// [`ParserOptions::raw_numbers`](crate::ParserOptions::raw_numbers) is
+    /// set.
+    RawNumber(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the value of the first member named `key`, if `self` is an
+    /// object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(key_values) => key_values
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the element at index `i`, if `self` is an array.
+    pub fn get_index(&self, i: usize) -> Option<&Value> {
+        match self {
+            Value::Array(array) => array.get(i),
+            _ => None,
+        }
+    }
+
+    /// Like [`get`](Value::get), but returns a mutable reference.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        match self {
+            Value::Object(key_values) => key_values
+                .iter_mut()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Like [`get_index`](Value::get_index), but returns a mutable reference.
+    pub fn get_index_mut(&mut self, i: usize) -> Option<&mut Value> {
+        match self {
+            Value::Array(array) => array.get_mut(i),
+            _ => None,
+        }
+    }
+
+    /// Returns the contents, if `self` is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns any scalar as text, for loosely typed input where `5`, `true`
+    /// and `"5"` should all be read as strings.
+    ///
+    /// A string gives its contents, and every other scalar the JSON text it
+    /// serializes as, so `null` gives `"null"`. Arrays and objects give
+    /// `None`.
+    pub fn coerce_to_string(&self) -> Option<String> {
+        match self {
+            Value::String(string) => Some(string.clone()),
+            Value::Array(_) | Value::Object(_) => None,
+            scalar => Some(scalar.serialize()),
+        }
+    }
+
+    /// Returns the number as an `f64`, if `self` is an integer or a number.
+    /// Integers beyond 2^53 lose precision.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(integer) => Some(*integer as f64),
+            Value::Number(number) => Some(*number),
+            Value::RawNumber(raw) => raw.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, if `self` is `true` or `false`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if `self` is an array.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+
+    /// Returns the members in source order, if `self` is an object.
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(key_values) => Some(key_values),
+            _ => None,
+        }
+    }
+
+    /// Returns whether `self` is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns whether `self` is an object.
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    /// Returns whether `self` is an array.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    /// Returns whether `self` is a string.
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    /// Returns whether `self` is a number, whether integer, float, or raw.
+    pub fn is_number(&self) -> bool {
+        matches!(
+            self,
+            Value::Integer(_) | Value::Number(_) | Value::RawNumber(_)
+        )
+    }
+
+    /// Names the kind of value, as JSON would: `"null"`, `"boolean"`,
+    /// `"string"`, `"number"`, `"array"` or `"object"`. Every numeric
+    /// variant is a `"number"`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::String(_) => "string",
+            Value::Integer(_) | Value::Number(_) | Value::RawNumber(_) => "number",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Returns the number of elements in an array or members in an object,
+    /// duplicates included. Anything else has a length of 0.
+    pub fn len(&self) -> usize {
+        match self {
+            Value::Array(array) => array.len(),
+            Value::Object(key_values) => key_values.len(),
+            _ => 0,
+        }
+    }
+
+    /// Returns whether `self` is an empty array, object, or string. Other
+    /// values are never empty, even though their [`len`](Value::len) is 0.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Value::Array(array) => array.is_empty(),
+            Value::Object(key_values) => key_values.is_empty(),
+            Value::String(string) => string.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Returns how deeply arrays and objects are nested: 0 for anything
+    /// else, 1 for an array or object holding no others, and so on.
+    pub fn depth(&self) -> usize {
+        let children = match self {
+            Value::Array(array) => array.iter().map(Value::depth).max(),
+            Value::Object(key_values) => key_values.iter().map(|(_, value)| value.depth()).max(),
+            _ => return 0,
+        };
+
+        children.unwrap_or(0) + 1
+    }
+
+    /// Lists every scalar leaf with its dotted path, e.g. `user.name` or
+    /// `items.0.id`, in document order.
+    ///
+    /// Array elements are named by index. Empty arrays and objects have no
+    /// leaves and so do not appear, and a scalar at the top level has the
+    /// empty path.
+    pub fn flatten(&self) -> Vec<(String, &Value)> {
+        let mut leaves = Vec::new();
+        self.flatten_into(String::new(), &mut leaves);
+        leaves
+    }
+
+    fn flatten_into<'a>(&'a self, path: String, leaves: &mut Vec<(String, &'a Value)>) {
+        let child = |segment: &dyn core::fmt::Display| match path.as_str() {
+            "" => segment.to_string(),
+            path => format!("{}.{}", path, segment),
+        };
+
+        match self {
+            Value::Array(array) => {
+                for (i, value) in array.iter().enumerate() {
+                    value.flatten_into(child(&i), leaves);
+                }
+            }
+            Value::Object(key_values) => {
+                for (key, value) in key_values {
+                    value.flatten_into(child(key), leaves);
+                }
+            }
+            _ => leaves.push((path, self)),
+        }
+    }
+
+    /// Iterates over the members of an object in source order. Anything
+    /// other than an object yields nothing.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.as_object()
+            .unwrap_or_default()
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns the members of an object sorted by key, leaving `self` in
+    /// source order. Anything other than an object yields nothing.
+    ///
+    /// Of several members with the same key only the last is kept, as most
+    /// JSON implementations resolve duplicates; note that
+    /// [`get`](Value::get) finds the first instead.
+    pub fn entries_sorted(&self) -> Vec<(&str, &Value)> {
+        self.entries()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Iterates over the elements of an array. Anything other than an array
+    /// yields nothing.
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.as_array().unwrap_or_default().iter()
+    }
+
+    /// Unwraps a string, or gives `self` back in a [`TypeError`] if it is
+    /// anything else.
+    pub fn into_string(self) -> Result<String, TypeError> {
+        match self {
+            Value::String(string) => Ok(string),
+            other => Err(TypeError::new("string", other)),
+        }
+    }
+
+    /// Unwraps an array's elements, or gives `self` back in a [`TypeError`]
+    /// if it is anything else.
+    pub fn into_array(self) -> Result<Vec<Value>, TypeError> {
+        match self {
+            Value::Array(array) => Ok(array),
+            other => Err(TypeError::new("array", other)),
+        }
+    }
+
+    /// Unwraps an object's members, or gives `self` back in a [`TypeError`]
+    /// if it is anything else.
+    pub fn into_object(self) -> Result<Vec<(String, Value)>, TypeError> {
+        match self {
+            Value::Object(key_values) => Ok(key_values),
+            other => Err(TypeError::new("object", other)),
+        }
+    }
+
+    /// Converts an object into a map for fast repeated lookups, keeping the
+    /// last value for duplicate keys, or gives `self` back in a
+    /// [`TypeError`] if it is anything else.
+    #[cfg(feature = "std")]
+    pub fn into_map(self) -> Result<HashMap<String, Value>, TypeError> {
+        match self {
+            Value::Object(key_values) => Ok(key_values.into_iter().collect()),
+            other => Err(TypeError::new("object", other)),
+        }
+    }
+
+    /// Like [`into_map`](Value::into_map), but copies the members out of a
+    /// borrowed object.
+    #[cfg(feature = "std")]
+    pub fn to_map(&self) -> Result<HashMap<String, Value>, TypeError> {
+        match self {
+            Value::Object(key_values) => Ok(key_values.iter().cloned().collect()),
+            other => Err(TypeError::new("object", other.clone())),
+        }
+    }
+
+    /// Removes and returns the value of the first member named `key`, if
+    /// `self` is an object. Later members shift down to take its place.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        match self {
+            Value::Object(key_values) => {
+                let i = key_values.iter().position(|(k, _)| k == key)?;
+                Some(key_values.remove(i).1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes and returns the element at index `i`, if `self` is an array.
+    /// Later elements shift down to take its place.
+    pub fn remove_index(&mut self, i: usize) -> Option<Value> {
+        match self {
+            Value::Array(array) if i < array.len() => Some(array.remove(i)),
+            _ => None,
+        }
+    }
+
+    /// Sets the value of the first member named `key`, returning the value
+    /// it replaced, or appends a new member if there is none.
+    ///
+    /// Fails with a copy of `self` in a [`TypeError`], changing nothing, if
+    /// `self` is not an object.
+    pub fn insert(
+        &mut self,
+        key: impl Into<String>,
+        value: Value,
+    ) -> Result<Option<Value>, TypeError> {
+        let key_values = match self {
+            Value::Object(key_values) => key_values,
+            other => return Err(TypeError::new("object", other.clone())),
+        };
+        let key = key.into();
+
+        match key_values.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => Ok(Some(mem::replace(existing, value))),
+            None => {
+                key_values.push((key, value));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Appends an element to the end of an array.
+    ///
+    /// Fails with a copy of `self` in a [`TypeError`], changing nothing, if
+    /// `self` is not an array.
+    pub fn push(&mut self, value: Value) -> Result<(), TypeError> {
+        match self {
+            Value::Array(array) => {
+                array.push(value);
+                Ok(())
+            }
+            other => Err(TypeError::new("array", other.clone())),
+        }
+    }
+
+    /// Keeps only the object members for which `f` returns `true`, visiting
+    /// them in order. Does nothing if `self` is not an object.
+    pub fn retain_entries(&mut self, mut f: impl FnMut(&str, &Value) -> bool) {
+        if let Value::Object(key_values) = self {
+            key_values.retain(|(key, value)| f(key, value));
+        }
+    }
+
+    /// Keeps only the array elements for which `f` returns `true`, visiting
+    /// them in order. Does nothing if `self` is not an array.
+    pub fn retain(&mut self, f: impl FnMut(&Value) -> bool) {
+        if let Value::Array(array) = self {
+            array.retain(f);
+        }
+    }
+
+    /// Deep-merges `other` on top of `self`.
+    ///
+    /// When both are objects, each member of `other` is merged into the
+    /// first member of `self` with the same key, or appended if there is
+    /// none, so repeated keys in `other` apply left to right. In every other
+    /// case, including arrays, `other` replaces `self` wholesale.
+    pub fn merge(&mut self, other: Value) {
+        match (self, other) {
+            (Value::Object(key_values), Value::Object(other)) => {
+                for (key, value) in other {
+                    match key_values.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, existing)) => existing.merge(value),
+                        None => key_values.push((key, value)),
+                    }
+                }
+            }
+            (this, other) => *this = other,
+        }
+    }
+
+    /// Compares two values, treating objects as unordered maps.
+    ///
+    /// Object members are matched by key, with the last of any duplicates
+    /// winning, so `{"a":1,"b":2}` equals `{"b":2,"a":1}`. Arrays must still
+    /// match in order, and [`Integer`](Value::Integer) and
+    /// [`Number`](Value::Number) compare by numeric value.
+    pub fn semantic_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Array(left), Value::Array(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(right)
+                        .all(|(left, right)| left.semantic_eq(right))
+            }
+            (Value::Object(left), Value::Object(right)) => {
+                let left: BTreeMap<&str, &Value> = left
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value))
+                    .collect();
+                let right: BTreeMap<&str, &Value> = right
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value))
+                    .collect();
+
+                left.len() == right.len()
+                    && left.iter().all(|(key, value)| {
+                        right.get(key).is_some_and(|other| value.semantic_eq(other))
+                    })
+            }
+            (Value::Integer(left), Value::Number(right))
+            | (Value::Number(right), Value::Integer(left)) => *left as f64 == *right,
+            _ => self == other,
+        }
+    }
+
+    /// Compares two values structurally, treating numbers as equal when they
+    /// are within `epsilon` of each other.
+    ///
+    /// Unlike [`semantic_eq`](Value::semantic_eq), object members must match
+    /// in order.
+    pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
+        match (self, other) {
+            (Value::Array(left), Value::Array(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(right)
+                        .all(|(left, right)| left.approx_eq(right, epsilon))
+            }
+            (Value::Object(left), Value::Object(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(right)
+                        .all(|((lk, lv), (rk, rv))| lk == rk && lv.approx_eq(rv, epsilon))
+            }
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(left), Some(right)) => left == right || (left - right).abs() <= epsilon,
+                _ => self == other,
+            },
+        }
+    }
+}
+
+/// The error from unwrapping a [`Value`] as a type it does not have, which
+/// gives the value back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    /// The [type name](Value::type_name) that was asked for.
+    pub expected: &'static str,
+    pub value: Value,
+}
+
+impl TypeError {
+    fn new(expected: &'static str, value: Value) -> Self {
+        TypeError { expected, value }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {}, found {}",
+            self.expected,
+            self.value.type_name()
+        )
+    }
+}
+
+impl core::error::Error for TypeError {}
+
+static NULL: Value = Value::Null;
+
+/// Looks up an object member like [`Value::get`].
+///
+/// Following serde_json, this never panics: a missing key, or indexing into
+/// anything other than an object, yields a reference to `Value::Null`.
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// Looks up an array element like [`Value::get_index`].
+///
+/// Following serde_json, this never panics: an out-of-range index, or
+/// indexing into anything other than an array, yields a reference to
+/// `Value::Null`.
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, i: usize) -> &Value {
+        self.get_index(i).unwrap_or(&NULL)
+    }
+}
+
+/// Lets values be used as `HashMap` keys and `HashSet` members.
+///
+/// A [`Number`](Value::Number) holding NaN breaks reflexivity, so such a
+/// value, or any array or object containing one, will never be found again
+/// once inserted. Strict JSON cannot express NaN, but it can be built by hand
+/// or parsed with [`allow_non_finite`](crate::ParserOptions::allow_non_finite)
+/// set, as [`parse_json5`](crate::parse_json5) does.
+impl Eq for Value {}
+
+/// Hashes consistently with the derived `PartialEq`.
+///
+/// Numbers hash by bit pattern, except that `-0.0` hashes as `0.0` since the
+/// two compare equal, and every NaN hashes alike. As with `==`, an
+/// [`Integer`](Value::Integer) never matches a [`Number`](Value::Number), so
+/// `1` and `1.0` may hash differently. Object members hash in stored order,
+/// so objects that are only [`semantic_eq`](Value::semantic_eq) will
+/// generally not collide.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        match self {
+            Value::Null => {}
+            Value::Bool(b) => b.hash(state),
+            Value::String(string) | Value::RawNumber(string) => string.hash(state),
+            Value::Integer(i) => i.hash(state),
+            Value::Number(n) => {
+                let bits = if *n == 0.0 {
+                    0.0f64.to_bits()
+                } else if n.is_nan() {
+                    f64::NAN.to_bits()
+                } else {
+                    n.to_bits()
+                };
+                bits.hash(state)
+            }
+            Value::Array(array) => array.hash(state),
+            Value::Object(key_values) => key_values.hash(state),
+        }
+    }
+}
+
+/// Parses a complete document, as [`parse`] does.
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::Null
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
+
+/// Smaller integers widen losslessly, which also lets integer literals such
+/// as those in [`json!`](crate::json) convert without a suffix.
+macro_rules! from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(value: $ty) -> Self {
+                    Value::Integer(value.into())
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, u8, u16, u32);
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(value: Vec<Value>) -> Self {
+        Value::Array(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TypeError;
+    use crate::{parse, parse_json5, ErrorKind, Value};
+
+    #[test]
+    fn test_get() {
+        let value = parse("{\"a\":{\"b\":[1,2]}}").unwrap();
+
+        assert_eq!(
+            value
+                .get("a")
+                .and_then(|a| a.get("b"))
+                .and_then(|b| b.get_index(1)),
+            Some(&Value::Integer(2))
+        );
+        assert_eq!(value.get("b"), None);
+        assert_eq!(value.get("a").and_then(|a| a.get_index(0)), None);
+        assert_eq!(value.get_index(0), None);
+        assert_eq!(
+            value
+                .get("a")
+                .and_then(|a| a.get("b"))
+                .and_then(|b| b.get_index(2)),
+            None
+        );
+        assert_eq!(Value::Null.get("a"), None);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut value = parse("{\"a\": {\"b\": [1, 2]}, \"a\": 3}").unwrap();
+
+        *value
+            .get_mut("a")
+            .and_then(|a| a.get_mut("b"))
+            .and_then(|b| b.get_index_mut(1))
+            .unwrap() = Value::from("two");
+        assert_eq!(value.serialize(), "{\"a\":{\"b\":[1,\"two\"]},\"a\":3}");
+
+        assert_eq!(value.get_mut("c"), None);
+        assert_eq!(value.get_index_mut(0), None);
+        assert_eq!(Value::Array(Vec::new()).get_index_mut(0), None);
+    }
+
+    #[test]
+    fn test_index() {
+        let value = parse("{\"users\":[{\"name\":\"ada\"},{\"name\":\"bob\"}]}").unwrap();
+
+        assert_eq!(value["users"][0]["name"], Value::String("ada".to_owned()));
+        assert_eq!(value["users"][1]["name"], Value::String("bob".to_owned()));
+        assert_eq!(value["users"][2]["name"], Value::Null);
+        assert_eq!(value["groups"], Value::Null);
+        assert_eq!(value["users"]["name"], Value::Null);
+        assert_eq!(value[0], Value::Null);
+        assert_eq!(Value::Integer(1)["a"][3], Value::Null);
+    }
+
+    #[test]
+    fn test_accessors() {
+        let value = parse("[\"s\", 1, 2.5, true, false, null, [0], {\"k\": 0}]").unwrap();
+        let values = value.as_array().unwrap();
+
+        assert_eq!(values.len(), 8);
+
+        assert_eq!(values[0].as_str(), Some("s"));
+        assert_eq!(values[1].as_str(), None);
+
+        assert_eq!(values[1].as_f64(), Some(1.0));
+        assert_eq!(values[2].as_f64(), Some(2.5));
+        assert_eq!(values[0].as_f64(), None);
+
+        assert_eq!(values[3].as_bool(), Some(true));
+        assert_eq!(values[4].as_bool(), Some(false));
+        assert_eq!(values[5].as_bool(), None);
+
+        assert_eq!(values[6].as_array(), Some(&[Value::Integer(0)][..]));
+        assert_eq!(values[7].as_array(), None);
+
+        assert_eq!(
+            values[7].as_object(),
+            Some(&[("k".to_owned(), Value::Integer(0))][..])
+        );
+        assert_eq!(values[6].as_object(), None);
+
+        assert!(values[5].is_null());
+        assert!(!values[1].is_null());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut value = parse(
+            "{\"name\":\"app\",\"db\":{\"host\":\"localhost\",\"port\":5432},\"tags\":[1,2]}",
+        )
+        .unwrap();
+        value.merge(
+            parse("{\"db\":{\"port\":6543,\"user\":\"admin\"},\"tags\":[3],\"debug\":true}")
+                .unwrap(),
+        );
+
+        assert_eq!(
+            Ok(value),
+            parse(
+                "{\"name\":\"app\",\"db\":{\"host\":\"localhost\",\"port\":6543,\"user\":\"admin\"},\"tags\":[3],\"debug\":true}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_type_mismatch() {
+        let mut value = parse("{\"a\":{\"b\":1},\"c\":[1],\"d\":2}").unwrap();
+        value.merge(parse("{\"a\":\"flat\",\"c\":{\"x\":1},\"d\":{\"y\":2}}").unwrap());
+
+        assert_eq!(
+            Ok(value),
+            parse("{\"a\":\"flat\",\"c\":{\"x\":1},\"d\":{\"y\":2}}")
+        );
+
+        let mut value = parse("{\"a\":1}").unwrap();
+        value.merge(Value::Array(Vec::new()));
+        assert_eq!(value, Value::Array(Vec::new()));
+
+        let mut value = Value::Null;
+        value.merge(parse("{\"a\":1}").unwrap());
+        assert_eq!(Ok(value), parse("{\"a\":1}"));
+    }
+
+    #[test]
+    fn test_merge_duplicate_keys() {
+        let mut value = parse("{\"a\":{\"x\":1}}").unwrap();
+        value.merge(parse("{\"a\":{\"y\":2},\"a\":{\"x\":3}}").unwrap());
+
+        assert_eq!(Ok(value), parse("{\"a\":{\"x\":3,\"y\":2}}"));
+    }
+
+    #[test]
+    fn test_into_map() {
+        let count = 10_000;
+        let input = format!(
+            "{{{}}}",
+            (0..count)
+                .map(|i| format!("\"key{}\":{}", i, i))
+                .collect::<Vec<_>>()
+                .join(",")
+        );
+        let map = parse(&input).unwrap().into_map().unwrap();
+
+        assert_eq!(map.len(), count);
+        for i in (0..count).step_by(7) {
+            assert_eq!(map[&format!("key{}", i)], Value::Integer(i as i64));
+        }
+        assert_eq!(map.get("missing"), None);
+    }
+
+    #[test]
+    fn test_into_map_duplicate_keys() {
+        let value = parse("{\"a\":1,\"b\":2,\"a\":3}").unwrap();
+        let map = value.to_map().unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], Value::Integer(3));
+        assert_eq!(map["b"], Value::Integer(2));
+        assert_eq!(value.into_map(), Ok(map));
+    }
+
+    #[test]
+    fn test_into_map_type_mismatch() {
+        let array = parse("[1]").unwrap();
+        let error = array.to_map().unwrap_err();
+
+        assert_eq!(error.to_string(), "expected object, found array");
+        assert_eq!(error.value, array);
+        assert_eq!(array.into_map().unwrap_err().expected, "object");
+        assert!(Value::Null.into_map().is_err());
+    }
+
+    #[test]
+    fn test_iterators() {
+        let value = parse("{\"b\":[1,2,3],\"a\":null,\"b\":{}}").unwrap();
+
+        assert_eq!(
+            value.entries().map(|(key, _)| key).collect::<Vec<_>>(),
+            ["b", "a", "b"]
+        );
+        assert_eq!(
+            value["b"].iter().collect::<Vec<_>>(),
+            [&Value::Integer(1), &Value::Integer(2), &Value::Integer(3)]
+        );
+
+        for (key, value) in value.entries() {
+            assert!(!key.is_empty());
+            assert_eq!(value.iter().count(), value.as_array().map_or(0, <[_]>::len));
+        }
+
+        assert_eq!(value.iter().count(), 0);
+        assert_eq!(value["b"].entries().count(), 0);
+        assert_eq!(Value::Null.entries().count(), 0);
+    }
+
+    #[test]
+    fn test_entries_sorted() {
+        let value = parse("{\"b\":1,\"a\":2,\"B\":3,\"b\":4}").unwrap();
+
+        assert_eq!(
+            value.entries_sorted(),
+            [
+                ("B", &Value::Integer(3)),
+                ("a", &Value::Integer(2)),
+                ("b", &Value::Integer(4))
+            ]
+        );
+        assert_eq!(value.entries().next(), Some(("b", &Value::Integer(1))));
+        assert!(parse("[1]").unwrap().entries_sorted().is_empty());
+    }
+
+    #[test]
+    fn test_get_duplicate_keys() {
+        let value = parse("{\"a\":1,\"a\":2}").unwrap();
+
+        assert_eq!(value.get("a"), Some(&Value::Integer(1)));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let value: Value = "{\"a\": [1, \"b\"]}".parse().unwrap();
+        assert_eq!(value, parse("{\"a\": [1, \"b\"]}").unwrap());
+
+        let error = "[1,".parse::<Value>().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::UnexpectedEof);
+        assert_eq!(error.offset, 3);
+    }
+
+    #[test]
+    fn test_from() {
+        let value = Value::Object(Vec::from([
+            ("name".to_owned(), "rjson".into()),
+            ("owner".to_owned(), String::from("acdibble").into()),
+            ("stable".to_owned(), false.into()),
+            ("stars".to_owned(), 12i64.into()),
+            ("score".to_owned(), 4.5.into()),
+            ("license".to_owned(), ().into()),
+            (
+                "tags".to_owned(),
+                Vec::from([Value::from("json"), true.into()]).into(),
+            ),
+        ]));
+
+        assert_eq!(
+            value.serialize(),
+            "{\"name\":\"rjson\",\"owner\":\"acdibble\",\"stable\":false,\"stars\":12,\"score\":4.5,\"license\":null,\"tags\":[\"json\",true]}"
+        );
+    }
+
+    #[test]
+    fn test_semantic_eq() {
+        let left = parse("{\"a\": 1, \"b\": {\"c\": [1, 2], \"d\": null}}").unwrap();
+        let right = parse("{\"b\": {\"d\": null, \"c\": [1, 2]}, \"a\": 1.0}").unwrap();
+        assert!(left != right);
+        assert!(left.semantic_eq(&right));
+        assert!(right.semantic_eq(&left));
+
+        let duplicates = parse("{\"a\": 0, \"b\": 2, \"a\": 1}").unwrap();
+        assert!(duplicates.semantic_eq(&parse("{\"b\": 2, \"a\": 1}").unwrap()));
+        assert!(!duplicates.semantic_eq(&parse("{\"b\": 2, \"a\": 0}").unwrap()));
+
+        assert!(!parse("[1, 2]")
+            .unwrap()
+            .semantic_eq(&parse("[2, 1]").unwrap()));
+        assert!(!parse("[1]").unwrap().semantic_eq(&parse("[1, 1]").unwrap()));
+        assert!(!parse("{\"a\": 1}")
+            .unwrap()
+            .semantic_eq(&parse("{\"b\": 1}").unwrap()));
+        assert!(!parse("{\"a\": 1}")
+            .unwrap()
+            .semantic_eq(&parse("{}").unwrap()));
+        assert!(!Value::Integer(1).semantic_eq(&Value::Number(1.5)));
+        assert!(!Value::String("1".to_owned()).semantic_eq(&Value::Integer(1)));
+    }
+
+    #[test]
+    fn test_len_and_depth() {
+        let flat = parse("[1, \"a\", null, true]").unwrap();
+        assert_eq!((flat.len(), flat.depth()), (4, 1));
+
+        let nested = parse("{\"a\": [1, [2, {\"b\": []}]], \"c\": {}, \"a\": 3}").unwrap();
+        assert_eq!((nested.len(), nested.depth()), (3, 5));
+        assert_eq!((nested["c"].len(), nested["c"].depth()), (0, 1));
+        assert!(nested["c"].is_empty());
+
+        assert_eq!((Value::Integer(1).len(), Value::Integer(1).depth()), (0, 0));
+        assert_eq!(Value::String("abc".to_owned()).len(), 0);
+        assert_eq!(parse("[]").unwrap().depth(), 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut value = parse("{\"a\": 1, \"b\": [true, null, \"c\"], \"a\": 2}").unwrap();
+
+        assert_eq!(value.remove("a"), Some(Value::Integer(1)));
+        assert_eq!(
+            value.entries().map(|(key, _)| key).collect::<Vec<_>>(),
+            ["b", "a"]
+        );
+        assert_eq!(value.remove("a"), Some(Value::Integer(2)));
+        assert_eq!(value.remove("a"), None);
+
+        let mut array = value.remove("b").unwrap();
+        assert_eq!(value, Value::Object(Vec::new()));
+        assert_eq!(array.remove_index(0), Some(Value::Bool(true)));
+        assert_eq!(array.remove_index(5), None);
+        assert_eq!(array, parse("[null, \"c\"]").unwrap());
+        assert_eq!(array.remove("a"), None);
+        assert_eq!(Value::Null.remove_index(0), None);
+    }
+
+    #[test]
+    fn test_insert_and_push() {
+        let mut value = parse("{\"a\": 1, \"list\": []}").unwrap();
+
+        assert_eq!(value.insert("b", Value::Bool(true)), Ok(None));
+        assert_eq!(
+            value.insert(String::from("a"), Value::Null),
+            Ok(Some(Value::Integer(1)))
+        );
+
+        let mut list = value.remove("list").unwrap();
+        list.push(Value::Integer(1)).unwrap();
+        list.push(Value::String("two".to_owned())).unwrap();
+        value.insert("list", list).unwrap();
+
+        assert_eq!(
+            value.serialize(),
+            "{\"a\":null,\"b\":true,\"list\":[1,\"two\"]}"
+        );
+    }
+
+    #[test]
+    fn test_insert_into_array() {
+        let mut array = parse("[1]").unwrap();
+        let error = array.insert("a", Value::Null).unwrap_err();
+
+        assert_eq!(error.expected, "object");
+        assert_eq!(error.to_string(), "expected object, found array");
+        assert_eq!(error.value, array);
+        assert_eq!(array, parse("[1]").unwrap());
+    }
+
+    #[test]
+    fn test_push_onto_object() {
+        let mut object = parse("{}").unwrap();
+        let error = object.push(Value::Null).unwrap_err();
+
+        assert_eq!(error.to_string(), "expected array, found object");
+        assert_eq!(object, parse("{}").unwrap());
+        assert!(Value::Null.push(Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_into_variants() {
+        assert_eq!(Value::from("a").into_string(), Ok("a".to_owned()));
+        assert_eq!(
+            parse("[1, null]").unwrap().into_array(),
+            Ok(Vec::from([Value::Integer(1), Value::Null]))
+        );
+        assert_eq!(
+            parse("{\"a\": true}").unwrap().into_object(),
+            Ok(Vec::from([("a".to_owned(), Value::Bool(true))]))
+        );
+
+        let error = parse("[\"a\"]").unwrap().into_string().unwrap_err();
+        assert_eq!(error.value, parse("[\"a\"]").unwrap());
+        assert_eq!(error.to_string(), "expected string, found array");
+        assert_eq!(
+            Value::from("a").into_array().unwrap_err().to_string(),
+            "expected array, found string"
+        );
+        assert_eq!(
+            Value::Null.into_object(),
+            Err(TypeError {
+                expected: "object",
+                value: Value::Null
+            })
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_string() {
+        let value = parse("[\"a\\\"b\", 5, -1.5, 1.0, true, false, null, [], {}]").unwrap();
+
+        assert_eq!(
+            value
+                .iter()
+                .map(Value::coerce_to_string)
+                .collect::<Vec<_>>(),
+            [
+                Some("a\"b".to_owned()),
+                Some("5".to_owned()),
+                Some("-1.5".to_owned()),
+                Some("1.0".to_owned()),
+                Some("true".to_owned()),
+                Some("false".to_owned()),
+                Some("null".to_owned()),
+                None,
+                None,
+            ]
+        );
+        assert_eq!(
+            Value::RawNumber("1.000000000000000001".to_owned()).coerce_to_string(),
+            Some("1.000000000000000001".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_type_name() {
+        let values = parse("[null, true, false, \"\", 1, 1.5, [], {}]").unwrap();
+
+        assert_eq!(
+            values.iter().map(Value::type_name).collect::<Vec<_>>(),
+            ["null", "boolean", "boolean", "string", "number", "number", "array", "object"]
+        );
+        assert_eq!(Value::RawNumber("1e400".to_owned()).type_name(), "number");
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let expected = parse("{\"a\": [0.3, 1, {\"b\": 2.5}], \"c\": \"d\"}").unwrap();
+        let computed = crate::json!({
+            "a": [0.1 + 0.2, 1.0000001, { "b": 2.5 - 1e-9 }],
+            "c": "d",
+        });
+
+        assert!(expected != computed);
+        assert!(expected.approx_eq(&computed, 1e-6));
+        assert!(!expected.approx_eq(&computed, 1e-12));
+        assert!(!Value::Integer(1).approx_eq(&Value::from("1"), 1.0));
+        assert!(!parse("{\"a\": 1, \"b\": 2}")
+            .unwrap()
+            .approx_eq(&parse("{\"b\": 2, \"a\": 1}").unwrap(), 0.0));
+        assert!(Value::Number(f64::INFINITY).approx_eq(&Value::Number(f64::INFINITY), 0.0));
+    }
+
+    #[test]
+    fn test_predicates() {
+        let values = parse("[{}, {\"a\": 1}, [], [1], \"\", \"a\", 0, 1.5, null, true]")
+            .unwrap()
+            .into_array()
+            .unwrap();
+        let check =
+            |predicate: fn(&Value) -> bool| values.iter().map(predicate).collect::<Vec<_>>();
+
+        let (t, f) = (true, false);
+        assert_eq!(check(Value::is_object), [t, t, f, f, f, f, f, f, f, f]);
+        assert_eq!(check(Value::is_array), [f, f, t, t, f, f, f, f, f, f]);
+        assert_eq!(check(Value::is_string), [f, f, f, f, t, t, f, f, f, f]);
+        assert_eq!(check(Value::is_number), [f, f, f, f, f, f, t, t, f, f]);
+        assert_eq!(check(Value::is_null), [f, f, f, f, f, f, f, f, t, f]);
+        assert_eq!(check(Value::is_empty), [t, f, t, f, t, f, f, f, f, f]);
+        assert!(Value::RawNumber("1".to_owned()).is_number());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut value = parse("{\"a\":null,\"b\":1,\"c\":null,\"d\":[null,2,null]}").unwrap();
+
+        value.retain_entries(|_, value| !value.is_null());
+        assert_eq!(value.serialize(), "{\"b\":1,\"d\":[null,2,null]}");
+
+        let array = value.get_mut("d").unwrap();
+        array.retain(|value| !value.is_null());
+        assert_eq!(array.serialize(), "[2]");
+
+        let mut string = Value::from("a");
+        string.retain(|_| false);
+        string.retain_entries(|_, _| false);
+        assert_eq!(string, Value::from("a"));
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        let mut set = HashSet::new();
+        assert!(set.insert(parse("{\"a\":[1,2.5,null]}").unwrap()));
+        assert!(!set.insert(parse("{ \"a\" : [ 1, 25e-1, null ] }").unwrap()));
+        assert!(set.insert(parse("{\"a\":[1,2.5,false]}").unwrap()));
+        assert!(set.insert(Value::Integer(0)));
+        assert!(set.insert(Value::Number(0.0)));
+        assert!(!set.insert(Value::Number(-0.0)));
+        assert!(set.insert(Value::from("0")));
+        assert_eq!(set.len(), 5);
+
+        // Hashing follows stored order, as `==` does.
+        assert!(set.insert(parse("{\"b\":1,\"c\":2}").unwrap()));
+        assert!(set.insert(parse("{\"c\":2,\"b\":1}").unwrap()));
+    }
+
+    #[test]
+    fn test_nan_eq() {
+        use std::collections::HashSet;
+
+        let nan = parse_json5("NaN").unwrap();
+        assert_ne!(nan, nan.clone());
+        assert_ne!(parse_json5("[NaN]").unwrap(), parse_json5("[NaN]").unwrap());
+        assert_eq!(
+            parse_json5("Infinity").unwrap(),
+            Value::Number(f64::INFINITY)
+        );
+
+        let mut set = HashSet::new();
+        assert!(set.insert(nan.clone()));
+        assert!(set.insert(nan.clone()));
+        assert!(!set.contains(&nan));
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_flatten() {
+        let value = parse(
+            "{\"user\": {\"name\": \"ada\", \"tags\": []}, \
+             \"items\": [{\"id\": 1}, {\"id\": 2, \"ok\": null}], \"n\": 1.5}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            value.flatten(),
+            [
+                ("user.name".to_owned(), &Value::from("ada")),
+                ("items.0.id".to_owned(), &Value::Integer(1)),
+                ("items.1.id".to_owned(), &Value::Integer(2)),
+                ("items.1.ok".to_owned(), &Value::Null),
+                ("n".to_owned(), &Value::Number(1.5)),
+            ]
+        );
+        assert_eq!(
+            Value::Bool(true).flatten(),
+            [(String::new(), &Value::Bool(true))]
+        );
+        assert!(parse("[[], {}]").unwrap().flatten().is_empty());
+    }
+}
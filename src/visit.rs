@@ -0,0 +1,181 @@
+use crate::Value;
+
+/// Callbacks for [`Value::walk`], each of which does nothing unless
+/// overridden.
+///
+/// Containers are reported by an `enter_` call before their contents and a
+/// matching `leave_` call after them; each object member's key is reported
+/// just before its value.
+pub trait Visitor {
+    fn visit_null(&mut self) {}
+
+    fn visit_bool(&mut self, _value: bool) {}
+
+    fn visit_string(&mut self, _value: &str) {}
+
+    /// Called for [`Integer`](Value::Integer), [`Number`](Value::Number) and
+    /// [`RawNumber`](Value::RawNumber) alike, with the node itself.
+    fn visit_number(&mut self, _value: &Value) {}
+
+    /// Called with the number of elements the array holds.
+    fn enter_array(&mut self, _len: usize) {}
+
+    fn leave_array(&mut self) {}
+
+    /// Called with the number of members the object holds.
+    fn enter_object(&mut self, _len: usize) {}
+
+    fn visit_key(&mut self, _key: &str) {}
+
+    fn leave_object(&mut self) {}
+}
+
+impl Value {
+    /// Traverses the value depth-first, in document order, reporting each
+    /// node to `visitor`.
+    pub fn walk(&self, visitor: &mut impl Visitor) {
+        match self {
+            Value::Null => visitor.visit_null(),
+            Value::Bool(value) => visitor.visit_bool(*value),
+            Value::String(string) => visitor.visit_string(string),
+            Value::Integer(_) | Value::Number(_) | Value::RawNumber(_) => {
+                visitor.visit_number(self)
+            }
+            Value::Array(array) => {
+                visitor.enter_array(array.len());
+                for value in array {
+                    value.walk(visitor);
+                }
+                visitor.leave_array();
+            }
+            Value::Object(key_values) => {
+                visitor.enter_object(key_values.len());
+                for (key, value) in key_values {
+                    visitor.visit_key(key);
+                    value.walk(visitor);
+                }
+                visitor.leave_object();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Visitor;
+    use crate::{parse, Value};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Counts {
+        nulls: usize,
+        bools: usize,
+        strings: usize,
+        numbers: usize,
+        arrays: usize,
+        objects: usize,
+        keys: usize,
+        depth: usize,
+        max_depth: usize,
+    }
+
+    impl Counts {
+        fn enter(&mut self) {
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+        }
+    }
+
+    impl Visitor for Counts {
+        fn visit_null(&mut self) {
+            self.nulls += 1;
+        }
+
+        fn visit_bool(&mut self, _value: bool) {
+            self.bools += 1;
+        }
+
+        fn visit_string(&mut self, _value: &str) {
+            self.strings += 1;
+        }
+
+        fn visit_number(&mut self, _value: &Value) {
+            self.numbers += 1;
+        }
+
+        fn enter_array(&mut self, _len: usize) {
+            self.arrays += 1;
+            self.enter();
+        }
+
+        fn leave_array(&mut self) {
+            self.depth -= 1;
+        }
+
+        fn enter_object(&mut self, _len: usize) {
+            self.objects += 1;
+            self.enter();
+        }
+
+        fn visit_key(&mut self, _key: &str) {
+            self.keys += 1;
+        }
+
+        fn leave_object(&mut self) {
+            self.depth -= 1;
+        }
+    }
+
+    #[test]
+    fn test_walk() {
+        let value = parse(
+            "{\"a\": [1, 2.5, \"x\", null], \"b\": {\"c\": true, \"d\": [[]]}, \"e\": \"y\"}",
+        )
+        .unwrap();
+
+        let mut counts = Counts::default();
+        value.walk(&mut counts);
+        assert_eq!(
+            counts,
+            Counts {
+                nulls: 1,
+                bools: 1,
+                strings: 2,
+                numbers: 2,
+                arrays: 3,
+                objects: 2,
+                keys: 5,
+                depth: 0,
+                max_depth: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_walk_order() {
+        struct Trace(Vec<String>);
+
+        impl Visitor for Trace {
+            fn visit_number(&mut self, value: &Value) {
+                self.0.push(value.serialize());
+            }
+
+            fn enter_array(&mut self, len: usize) {
+                self.0.push(format!("[{}", len));
+            }
+
+            fn leave_array(&mut self) {
+                self.0.push("]".to_owned());
+            }
+
+            fn visit_key(&mut self, key: &str) {
+                self.0.push(key.to_owned());
+            }
+        }
+
+        let mut trace = Trace(Vec::new());
+        parse("{\"a\": [1, [2]], \"b\": 3}")
+            .unwrap()
+            .walk(&mut trace);
+        assert_eq!(trace.0, ["a", "[2", "1", "[1", "2", "]", "]", "b", "3"]);
+    }
+}
@@ -0,0 +1,175 @@
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn rjson(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rjson"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs the binary with `input` piped to its stdin.
+///
+/// The binary may reject its arguments and exit without reading the input,
+/// so a broken pipe while writing it is not an error.
+fn rjson_stdin(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rjson"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let written = child.stdin.take().unwrap().write_all(input.as_bytes());
+    if let Err(error) = written {
+        assert_eq!(error.kind(), ErrorKind::BrokenPipe, "{}", error);
+    }
+    child.wait_with_output().unwrap()
+}
+
+/// Writes `contents` to a file in the temp dir that is unique to `name`.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rjson-{}-{}.json", name, std::process::id()));
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_missing_file() {
+    let output = rjson(&["does/not/exist.json"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).starts_with("error: cannot open does/not/exist.json: "),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_malformed_file() {
+    let path = temp_file("malformed", "[1,\n  x]");
+    let output = rjson(&[path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        format!(
+            "error: {}: Unexpected token 'x' at line 2, column 3\n",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn test_reformat() {
+    let path = temp_file("reformat", "{ \"a\" : [1, 2.5],\n\"b\": {} }");
+    let path = path.to_str().unwrap();
+    let pretty = rjson(&["--pretty", path]);
+    let minified = rjson(&[path, "--minify"]);
+    let unknown = rjson(&["--shout", path]);
+    fs::remove_file(path).unwrap();
+
+    assert!(pretty.status.success());
+    assert_eq!(
+        String::from_utf8(pretty.stdout).unwrap(),
+        "{\n  \"a\": [\n    1,\n    2.5\n  ],\n  \"b\": {}\n}\n"
+    );
+    assert!(minified.status.success());
+    assert_eq!(
+        String::from_utf8(minified.stdout).unwrap(),
+        "{\"a\":[1,2.5],\"b\":{}}\n"
+    );
+    assert_eq!(unknown.status.code(), Some(1));
+    assert_eq!(stderr(&unknown), "error: unknown option --shout\n");
+}
+
+#[test]
+fn test_in_place() {
+    let path = temp_file("in-place", "{ \"a\" : [1, 2.5],\n\"b\": {} }\n");
+    let bare = temp_file("in-place-bare", "[ 1 ]");
+    let malformed = temp_file("in-place-malformed", "[1,");
+
+    let pretty = rjson(&["--pretty", "--in-place", path.to_str().unwrap()]);
+    let pretty_contents = fs::read_to_string(&path).unwrap();
+    let minified = rjson(&["--in-place", "--minify", path.to_str().unwrap()]);
+    let minified_contents = fs::read_to_string(&path).unwrap();
+    let bare_output = rjson(&["--minify", "--in-place", bare.to_str().unwrap()]);
+    let bare_contents = fs::read_to_string(&bare).unwrap();
+    let malformed_output = rjson(&["--pretty", "--in-place", malformed.to_str().unwrap()]);
+    let malformed_contents = fs::read_to_string(&malformed).unwrap();
+    let timed = rjson(&["--in-place", path.to_str().unwrap()]);
+    for path in [&path, &bare, &malformed] {
+        fs::remove_file(path).unwrap();
+    }
+
+    assert!(pretty.status.success());
+    assert!(pretty.stdout.is_empty());
+    assert_eq!(
+        pretty_contents,
+        "{\n  \"a\": [\n    1,\n    2.5\n  ],\n  \"b\": {}\n}\n"
+    );
+    assert!(minified.status.success());
+    assert_eq!(minified_contents, "{\"a\":[1,2.5],\"b\":{}}\n");
+    assert!(bare_output.status.success());
+    assert_eq!(bare_contents, "[1]");
+
+    assert_eq!(malformed_output.status.code(), Some(1));
+    assert_eq!(malformed_contents, "[1,");
+    assert_eq!(timed.status.code(), Some(1));
+    assert_eq!(
+        stderr(&timed),
+        "error: --in-place needs --pretty or --minify and a FILE\n"
+    );
+
+    let output = rjson_stdin(&["--minify", "--in-place"], "[]");
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_stdin() {
+    let output = rjson_stdin(&["--minify"], "[ 1, { \"a\" : null } ]");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "[1,{\"a\":null}]\n"
+    );
+
+    let output = rjson_stdin(&["--minify", "-"], "true");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "true\n");
+
+    let output = rjson_stdin(&[], "[1,");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stderr(&output), "error: <stdin>: Unexpected end of input\n");
+}
+
+#[cfg(all(feature = "mmap", unix))]
+#[test]
+fn test_mmap() {
+    let path = temp_file("mmap", "{ \"a\" : [1, 2.5] }");
+    let empty = temp_file("mmap-empty", "");
+    let output = rjson(&["--mmap", "--minify", path.to_str().unwrap()]);
+    let empty_output = rjson(&["--mmap", empty.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&empty).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"a\":[1,2.5]}\n"
+    );
+    assert_eq!(
+        stderr(&empty_output),
+        format!("error: {}: Empty input\n", empty.display())
+    );
+
+    let output = rjson(&["--mmap", "does/not/exist.json"]);
+    assert!(stderr(&output).starts_with("error: cannot open does/not/exist.json: "));
+}
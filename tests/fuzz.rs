@@ -0,0 +1,76 @@
+//! Feeds pseudo-random input to every entry point to check that malformed
+//! documents are reported as errors rather than panics.
+
+use rjson::{
+    parse, parse_borrowed, parse_bytes, parse_json5, parse_lenient_collect, validate, StreamParser,
+};
+
+/// A xorshift generator, so that failures reproduce without a dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Fragments that are likely to combine into nearly valid documents, so the
+/// input reaches deep into the grammar instead of failing on the first byte.
+const FRAGMENTS: &[&str] = &[
+    "{", "}", "[", "]", ",", ":", "\"", "\\", "\\u", "\\ud83d", "0", "1", "-", "+", ".", "e", "E",
+    "9", "null", "true", "fals", "\"a\"", " ", "\n", "//", "/*", "*/", "é", "\u{0}", "1e400",
+    "\\n",
+];
+
+fn check(input: &str) {
+    let parsed = parse(input);
+    assert_eq!(
+        validate(input).err().as_ref(),
+        parsed.as_ref().err(),
+        "{:?}",
+        input
+    );
+    assert_eq!(
+        parse_borrowed(input).map(|value| value.into_owned()),
+        parsed,
+        "{:?}",
+        input
+    );
+    let (value, errors) = parse_lenient_collect(input);
+    match &parsed {
+        Ok(_) => assert_eq!((value.as_ref(), errors.len()), (parsed.as_ref().ok(), 0)),
+        Err(error) => assert_eq!(errors.first(), Some(error), "{:?}", input),
+    }
+    let _ = parse_json5(input);
+    let _ = StreamParser::new(input).count();
+}
+
+#[test]
+fn test_random_bytes() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..5_000 {
+        let bytes: Vec<u8> = (0..rng.below(64)).map(|_| rng.next() as u8).collect();
+        let _ = parse_bytes(&bytes);
+        check(&String::from_utf8_lossy(&bytes));
+    }
+}
+
+#[test]
+fn test_random_fragments() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..20_000 {
+        let input: String = (0..rng.below(32))
+            .map(|_| FRAGMENTS[rng.below(FRAGMENTS.len())])
+            .collect();
+        check(&input);
+    }
+}
@@ -0,0 +1,33 @@
+//! Checks that the library still builds as `no_std` once the default `std`
+//! feature is turned off.
+
+use std::process::Command;
+
+#[test]
+fn test_no_std_build() {
+    for features in ["", "serde"] {
+        let output = Command::new(env!("CARGO"))
+            .args([
+                "check",
+                "--lib",
+                "--no-default-features",
+                "--features",
+                features,
+            ])
+            .arg("--manifest-path")
+            .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+            // A separate target dir avoids waiting on the lock held by the
+            // build running this test.
+            .arg("--target-dir")
+            .arg(concat!(env!("CARGO_TARGET_TMPDIR"), "/no_std"))
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "features {:?}:\n{}",
+            features,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}